@@ -0,0 +1,18 @@
+// Regenerates the expected output of `normalize` with GraphQL.js:
+//
+//   cd data && npm install && npm run generate
+import { readFileSync, writeFileSync } from 'node:fs'
+import { parse, print, stripIgnoredCharacters } from 'graphql'
+
+const fixtures = {
+  'normalize_query.gql': 'normalize_query_expected.gql',
+  'normalize_schema.gql': 'normalize_schema_expected.gql',
+  'kitchen_sink_schema.gql': 'kitchen_sink_schema_normalized.gql',
+}
+
+for (const [source, expected] of Object.entries(fixtures)) {
+  const document = readFileSync(new URL(source, import.meta.url), 'utf8')
+  const normalized = stripIgnoredCharacters(print(parse(document)))
+
+  writeFileSync(new URL(expected, import.meta.url), normalized)
+}
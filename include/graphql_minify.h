@@ -0,0 +1,68 @@
+#ifndef GRAPHQL_MINIFY_H
+#define GRAPHQL_MINIFY_H
+
+/* Generated with cbindgen, do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Outcome of [`graphql_minify_minify`].
+ */
+typedef enum GraphQLMinifyStatus {
+  GraphQLMinifyStatus_Ok = 0,
+  GraphQLMinifyStatus_UnknownToken = 1,
+  GraphQLMinifyStatus_UnterminatedString = 2,
+  GraphQLMinifyStatus_InvalidUtf8 = 3,
+  GraphQLMinifyStatus_NullPointer = 4,
+  GraphQLMinifyStatus_InvalidEscapeSequence = 5,
+  GraphQLMinifyStatus_InvalidNumber = 6,
+} GraphQLMinifyStatus;
+
+/**
+ * Filled in by [`graphql_minify_minify`] and released with [`graphql_minify_result_free`].
+ *
+ * On success `minified` points to a NUL-terminated string of `minified_len` bytes owned by this
+ * library. Otherwise `minified` is null and `error_offset` is the byte offset of the error, with
+ * `error_line` and `error_column` being its one-based position.
+ */
+typedef struct GraphQLMinifyResult {
+  char *minified;
+  size_t minified_len;
+  size_t error_offset;
+  size_t error_line;
+  size_t error_column;
+} GraphQLMinifyResult;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Minifies the NUL-terminated, UTF-8 encoded document `query` into `result`.
+ *
+ * # Safety
+ *
+ * `query` must be null or point to a NUL-terminated string, `result` must be null or point to
+ * writable memory for a [`GraphQLMinifyResult`]. Every result written by this function has to be
+ * released with [`graphql_minify_result_free`].
+ */
+GraphQLMinifyStatus graphql_minify_minify(const char *query, struct GraphQLMinifyResult *result);
+
+/**
+ * Releases the string owned by `result` and resets it, so freeing a result twice is harmless.
+ *
+ * # Safety
+ *
+ * `result` must be null or point to a [`GraphQLMinifyResult`] written by [`graphql_minify_minify`].
+ */
+void graphql_minify_result_free(struct GraphQLMinifyResult *result);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* GRAPHQL_MINIFY_H */
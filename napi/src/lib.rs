@@ -0,0 +1,106 @@
+use graphql_minify::LexingError;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+#[napi(object)]
+pub struct MinifyOptions {
+  /// `"minify"` (the default) strips ignored characters, `"strict"` also rejects numbers that are
+  /// directly followed by a name or a dot and punctuators outside of the spec such as `?`, and
+  /// `"normalize"` returns the exact output of GraphQL.js'
+  /// `stripIgnoredCharacters(print(parse(query)))`.
+  #[napi(ts_type = "'minify' | 'strict' | 'normalize'")]
+  pub mode: Option<String>,
+}
+
+#[napi]
+pub fn minify(query: String, options: Option<MinifyOptions>) -> Result<String> {
+  minifier(mode(options).as_deref())?(&query)
+}
+
+/// Minifies every document in `queries`, failing on the first one that can not be minified.
+#[napi]
+pub fn minify_batch(queries: Vec<String>, options: Option<MinifyOptions>) -> Result<Vec<String>> {
+  let minify = minifier(mode(options).as_deref())?;
+
+  queries.iter().map(|query| minify(query)).collect()
+}
+
+/// Same as `minify`, but runs on the libuv thread pool instead of blocking the event loop.
+#[napi]
+pub fn minify_async(query: String, options: Option<MinifyOptions>) -> AsyncTask<MinifyTask> {
+  AsyncTask::new(MinifyTask(query, mode(options)))
+}
+
+/// Same as `minifyBatch`, but runs on the libuv thread pool instead of blocking the event loop.
+#[napi]
+pub fn minify_batch_async(
+  queries: Vec<String>,
+  options: Option<MinifyOptions>,
+) -> AsyncTask<MinifyBatchTask> {
+  AsyncTask::new(MinifyBatchTask(queries, mode(options)))
+}
+
+pub struct MinifyTask(String, Option<String>);
+
+impl Task for MinifyTask {
+  type Output = String;
+  type JsValue = String;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    minifier(self.1.as_deref())?(&self.0)
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+pub struct MinifyBatchTask(Vec<String>, Option<String>);
+
+impl Task for MinifyBatchTask {
+  type Output = Vec<String>;
+  type JsValue = Vec<String>;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    let minify = minifier(self.1.as_deref())?;
+
+    self.0.iter().map(|query| minify(query)).collect()
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+type Minifier = fn(&str) -> Result<String>;
+
+fn mode(options: Option<MinifyOptions>) -> Option<String> {
+  options.and_then(|options| options.mode)
+}
+
+fn minifier(mode: Option<&str>) -> Result<Minifier> {
+  match mode {
+    None | Some("minify") => {
+      Ok(|query| graphql_minify::minify(query).map_err(|e| lexing_error(e, query)))
+    }
+    Some("strict") => {
+      Ok(|query| graphql_minify::minify_strict(query).map_err(|e| lexing_error(e, query)))
+    }
+    Some("normalize") => {
+      Ok(|query| graphql_minify::normalize(query).map_err(|e| lexing_error(e, query)))
+    }
+    Some(mode) => Err(Error::new(
+      Status::InvalidArg,
+      format!("unknown minify mode \"{mode}\""),
+    )),
+  }
+}
+
+fn lexing_error(error: LexingError, source: &str) -> Error {
+  let (line, column) = error.location(source);
+
+  Error::new(
+    Status::InvalidArg,
+    format!("{error} (line {line}, column {column})"),
+  )
+}
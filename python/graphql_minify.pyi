@@ -0,0 +1,11 @@
+from typing import Literal
+
+class GraphQLMinifyError(ValueError):
+    offset: int
+    line: int
+    column: int
+
+Mode = Literal["minify", "strict", "normalize"]
+
+def minify(query: str, *, mode: Mode = "minify") -> str: ...
+def minify_many(queries: list[str], *, mode: Mode = "minify") -> list[str]: ...
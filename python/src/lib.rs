@@ -0,0 +1,146 @@
+use graphql_minify::LexingError;
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+create_exception!(
+  graphql_minify,
+  GraphQLMinifyError,
+  PyValueError,
+  "Raised when a document can not be minified, `offset`, `line` and `column` locate the error."
+);
+
+/// Strips characters that are not significant to the validity or execution of a GraphQL document.
+///
+/// `mode="strict"` also rejects numbers that are directly followed by a name or a dot and
+/// punctuators outside of the spec such as `?`, and `mode="normalize"` returns the exact output of
+/// GraphQL.js' `stripIgnoredCharacters(print(parse(query)))`.
+#[pyfunction]
+#[pyo3(signature = (query, *, mode = "minify"))]
+fn minify(py: Python<'_>, query: &str, mode: &str) -> PyResult<String> {
+  let minify = minifier(mode)?;
+
+  py.allow_threads(|| minify(query))
+    .map_err(|e| lexing_error(py, e, query))
+}
+
+/// Minifies every document in `queries`, failing on the first one that can not be minified.
+#[pyfunction]
+#[pyo3(signature = (queries, *, mode = "minify"))]
+fn minify_many(py: Python<'_>, queries: Vec<String>, mode: &str) -> PyResult<Vec<String>> {
+  let minify = minifier(mode)?;
+
+  py.allow_threads(|| {
+    queries
+      .iter()
+      .map(|query| minify(query).map_err(|e| (e, query)))
+      .collect::<Result<Vec<_>, _>>()
+  })
+  .map_err(|(e, query)| lexing_error(py, e, query))
+}
+
+type Minifier = fn(&str) -> Result<String, LexingError>;
+
+fn minifier(mode: &str) -> PyResult<Minifier> {
+  match mode {
+    "minify" => Ok(|query| graphql_minify::minify(query)),
+    "strict" => Ok(|query| graphql_minify::minify_strict(query)),
+    "normalize" => Ok(|query| graphql_minify::normalize(query)),
+    _ => Err(PyValueError::new_err(format!(
+      "unknown minify mode \"{mode}\""
+    ))),
+  }
+}
+
+fn lexing_error(py: Python<'_>, error: LexingError, source: &str) -> PyErr {
+  let (line, column) = error.location(source);
+  // Python indexes strings by code point rather than by byte.
+  let offset = source[..error.offset().min(source.len())].chars().count();
+
+  let err = GraphQLMinifyError::new_err(format!("{error} (line {line}, column {column})"));
+  let value = err.value(py);
+  for (name, position) in [("offset", offset), ("line", line), ("column", column)] {
+    if let Err(e) = value.setattr(name, position) {
+      return e;
+    }
+  }
+
+  err
+}
+
+#[pymodule]
+#[pyo3(name = "graphql_minify")]
+fn graphql_minify_py(py: Python<'_>, m: &PyModule) -> PyResult<()> {
+  m.add_function(wrap_pyfunction!(minify, m)?)?;
+  m.add_function(wrap_pyfunction!(minify_many, m)?)?;
+  m.add("GraphQLMinifyError", py.get_type::<GraphQLMinifyError>())?;
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use pyo3::types::IntoPyDict;
+
+  fn with_module<F: FnOnce(Python<'_>, &PyModule)>(f: F) {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+      let module = PyModule::new(py, "graphql_minify").unwrap();
+      graphql_minify_py(py, module).unwrap();
+      f(py, module);
+    });
+  }
+
+  #[test]
+  fn minifies_with_mode() {
+    with_module(|_, module| {
+      let minify = module.getattr("minify").unwrap();
+
+      let minified: String = minify.call1(("{ a }",)).unwrap().extract().unwrap();
+      assert_eq!(minified, "{a}");
+
+      let kwargs = [("mode", "normalize")].into_py_dict(module.py());
+      let normalized: String = minify
+        .call(("query { a }",), Some(kwargs))
+        .unwrap()
+        .extract()
+        .unwrap();
+      assert_eq!(normalized, "{a}");
+    });
+  }
+
+  #[test]
+  fn raises_errors_with_position() {
+    with_module(|py, module| {
+      let kwargs = [("mode", "strict")].into_py_dict(py);
+      let err = module
+        .getattr("minify")
+        .unwrap()
+        .call(("{ a(b: \"ä\")\n  c(d: 1x) }",), Some(kwargs))
+        .unwrap_err();
+
+      assert!(err.is_instance_of::<GraphQLMinifyError>(py));
+      let value = err.value(py);
+      let position = |name| value.getattr(name).unwrap().extract::<usize>().unwrap();
+      assert_eq!(position("offset"), 20);
+      assert_eq!(position("line"), 2);
+      assert_eq!(position("column"), 9);
+    });
+  }
+
+  #[test]
+  fn raises_value_errors_for_unknown_modes() {
+    with_module(|py, module| {
+      let kwargs = [("mode", "pretty")].into_py_dict(py);
+      let err = module
+        .getattr("minify_many")
+        .unwrap()
+        .call((vec!["{ a }"],), Some(kwargs))
+        .unwrap_err();
+
+      assert!(err.is_instance_of::<PyValueError>(py));
+      assert!(!err.is_instance_of::<GraphQLMinifyError>(py));
+    });
+  }
+}
@@ -0,0 +1,72 @@
+//! Minified printing of documents parsed by other crates, without printing and lexing them again.
+//!
+//! String values and descriptions are always printed as regular strings, block strings are not
+//! preserved by these ASTs.
+
+#[cfg(feature = "apollo-compiler")]
+mod apollo_compiler;
+#[cfg(feature = "graphql-parser")]
+mod graphql_parser;
+
+use crate::lexer::Token;
+use crate::printer::Printer;
+use crate::string::print_string;
+
+/// A parsed GraphQL document that can be printed in minified form.
+pub trait MinifyAst {
+  /// Prints the document with the same spacing rules as [`minify`](crate::minify).
+  fn minify_ast(&self) -> String;
+}
+
+/// Prints an already parsed document in minified form, see [`MinifyAst`].
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "graphql-parser")]
+/// # {
+/// use graphql_minify::minify_ast;
+///
+/// let document = graphql_parser::parse_query::<&str>("query { user { id name } }").unwrap();
+///
+/// assert_eq!(minify_ast(&document), "query{user{id name}}");
+/// # }
+/// ```
+pub fn minify_ast<T: MinifyAst + ?Sized>(document: &T) -> String {
+  document.minify_ast()
+}
+
+fn name(printer: &mut Printer, name: &str) {
+  printer.push(Token::Identifier(name));
+}
+
+fn variable(printer: &mut Printer, name: &str) {
+  printer.push(Token::Variable(&format!("${name}")));
+}
+
+fn directive(printer: &mut Printer, name: &str) {
+  printer.push(Token::Directive(&format!("@{name}")));
+}
+
+fn string(printer: &mut Printer, value: &str) {
+  printer.push(Token::String(&print_string(value)));
+}
+
+/// Prints `items` between `open` and `close`, nothing at all if there are none.
+fn list<T>(
+  printer: &mut Printer,
+  open: Token,
+  close: Token,
+  items: &[T],
+  mut item: impl FnMut(&mut Printer, &T),
+) {
+  if items.is_empty() {
+    return;
+  }
+
+  printer.push(open);
+  for value in items {
+    item(printer, value);
+  }
+  printer.push(close);
+}
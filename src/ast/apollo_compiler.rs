@@ -0,0 +1,478 @@
+use super::{directive, list, name, string, variable, MinifyAst};
+use crate::lexer::Token;
+use crate::printer::Printer;
+use apollo_compiler::ast::{
+  Argument, Definition, DirectiveList, Document, EnumValueDefinition, FieldDefinition,
+  InputValueDefinition, OperationType, Selection, Type, Value, VariableDefinition,
+};
+use apollo_compiler::{Name, Node};
+
+impl MinifyAst for Document {
+  fn minify_ast(&self) -> String {
+    let mut printer = Printer::default();
+
+    for definition in &self.definitions {
+      print_definition(&mut printer, definition);
+    }
+
+    printer.finish()
+  }
+}
+
+fn print_definition(printer: &mut Printer, definition: &Definition) {
+  match definition {
+    Definition::OperationDefinition(operation) => {
+      let is_shorthand = operation.operation_type == OperationType::Query
+        && operation.name.is_none()
+        && operation.variables.is_empty()
+        && operation.directives.is_empty();
+
+      if !is_shorthand {
+        name(printer, operation.operation_type.name());
+        if let Some(operation_name) = &operation.name {
+          name(printer, operation_name);
+        }
+        print_variable_definitions(printer, &operation.variables);
+        print_directives(printer, &operation.directives);
+      }
+      print_selection_set(printer, &operation.selection_set);
+    }
+    Definition::FragmentDefinition(fragment) => {
+      name(printer, "fragment");
+      name(printer, &fragment.name);
+      name(printer, "on");
+      name(printer, &fragment.type_condition);
+      print_directives(printer, &fragment.directives);
+      print_selection_set(printer, &fragment.selection_set);
+    }
+    Definition::DirectiveDefinition(definition) => {
+      print_description(printer, &definition.description);
+      name(printer, "directive");
+      directive(printer, &definition.name);
+      print_argument_definitions(printer, &definition.arguments);
+      if definition.repeatable {
+        name(printer, "repeatable");
+      }
+      name(printer, "on");
+      separated(
+        printer,
+        Token::Pipe,
+        &definition.locations,
+        |printer, location| name(printer, location.name()),
+      );
+    }
+    Definition::SchemaDefinition(definition) => {
+      print_description(printer, &definition.description);
+      name(printer, "schema");
+      print_directives(printer, &definition.directives);
+      print_root_operations(printer, &definition.root_operations);
+    }
+    Definition::ScalarTypeDefinition(definition) => {
+      print_description(printer, &definition.description);
+      name(printer, "scalar");
+      name(printer, &definition.name);
+      print_directives(printer, &definition.directives);
+    }
+    Definition::ObjectTypeDefinition(definition) => {
+      print_description(printer, &definition.description);
+      name(printer, "type");
+      name(printer, &definition.name);
+      print_implements_interfaces(printer, &definition.implements_interfaces);
+      print_directives(printer, &definition.directives);
+      print_field_definitions(printer, &definition.fields);
+    }
+    Definition::InterfaceTypeDefinition(definition) => {
+      print_description(printer, &definition.description);
+      name(printer, "interface");
+      name(printer, &definition.name);
+      print_implements_interfaces(printer, &definition.implements_interfaces);
+      print_directives(printer, &definition.directives);
+      print_field_definitions(printer, &definition.fields);
+    }
+    Definition::UnionTypeDefinition(definition) => {
+      print_description(printer, &definition.description);
+      name(printer, "union");
+      name(printer, &definition.name);
+      print_directives(printer, &definition.directives);
+      print_union_members(printer, &definition.members);
+    }
+    Definition::EnumTypeDefinition(definition) => {
+      print_description(printer, &definition.description);
+      name(printer, "enum");
+      name(printer, &definition.name);
+      print_directives(printer, &definition.directives);
+      print_enum_values(printer, &definition.values);
+    }
+    Definition::InputObjectTypeDefinition(definition) => {
+      print_description(printer, &definition.description);
+      name(printer, "input");
+      name(printer, &definition.name);
+      print_directives(printer, &definition.directives);
+      print_input_fields(printer, &definition.fields);
+    }
+    Definition::SchemaExtension(extension) => {
+      name(printer, "extend");
+      name(printer, "schema");
+      print_directives(printer, &extension.directives);
+      print_root_operations(printer, &extension.root_operations);
+    }
+    Definition::ScalarTypeExtension(extension) => {
+      name(printer, "extend");
+      name(printer, "scalar");
+      name(printer, &extension.name);
+      print_directives(printer, &extension.directives);
+    }
+    Definition::ObjectTypeExtension(extension) => {
+      name(printer, "extend");
+      name(printer, "type");
+      name(printer, &extension.name);
+      print_implements_interfaces(printer, &extension.implements_interfaces);
+      print_directives(printer, &extension.directives);
+      print_field_definitions(printer, &extension.fields);
+    }
+    Definition::InterfaceTypeExtension(extension) => {
+      name(printer, "extend");
+      name(printer, "interface");
+      name(printer, &extension.name);
+      print_implements_interfaces(printer, &extension.implements_interfaces);
+      print_directives(printer, &extension.directives);
+      print_field_definitions(printer, &extension.fields);
+    }
+    Definition::UnionTypeExtension(extension) => {
+      name(printer, "extend");
+      name(printer, "union");
+      name(printer, &extension.name);
+      print_directives(printer, &extension.directives);
+      print_union_members(printer, &extension.members);
+    }
+    Definition::EnumTypeExtension(extension) => {
+      name(printer, "extend");
+      name(printer, "enum");
+      name(printer, &extension.name);
+      print_directives(printer, &extension.directives);
+      print_enum_values(printer, &extension.values);
+    }
+    Definition::InputObjectTypeExtension(extension) => {
+      name(printer, "extend");
+      name(printer, "input");
+      name(printer, &extension.name);
+      print_directives(printer, &extension.directives);
+      print_input_fields(printer, &extension.fields);
+    }
+  }
+}
+
+fn print_description(printer: &mut Printer, description: &Option<Node<str>>) {
+  if let Some(description) = description {
+    string(printer, description);
+  }
+}
+
+fn print_root_operations(printer: &mut Printer, operations: &[Node<(OperationType, Name)>]) {
+  list(
+    printer,
+    Token::BraceOpen,
+    Token::BraceClose,
+    operations,
+    |printer, operation| {
+      let (operation_type, type_name) = &**operation;
+      name(printer, operation_type.name());
+      printer.push(Token::Colon);
+      name(printer, type_name);
+    },
+  );
+}
+
+fn print_implements_interfaces(printer: &mut Printer, interfaces: &[Name]) {
+  if !interfaces.is_empty() {
+    name(printer, "implements");
+    separated(
+      printer,
+      Token::Ampersand,
+      interfaces,
+      |printer, interface| name(printer, interface),
+    );
+  }
+}
+
+fn print_union_members(printer: &mut Printer, members: &[Name]) {
+  if !members.is_empty() {
+    printer.push(Token::Equals);
+    separated(printer, Token::Pipe, members, |printer, member| {
+      name(printer, member)
+    });
+  }
+}
+
+fn print_field_definitions(printer: &mut Printer, fields: &[Node<FieldDefinition>]) {
+  list(
+    printer,
+    Token::BraceOpen,
+    Token::BraceClose,
+    fields,
+    |printer, field| {
+      print_description(printer, &field.description);
+      name(printer, &field.name);
+      print_argument_definitions(printer, &field.arguments);
+      printer.push(Token::Colon);
+      print_type(printer, &field.ty);
+      print_directives(printer, &field.directives);
+    },
+  );
+}
+
+fn print_argument_definitions(printer: &mut Printer, arguments: &[Node<InputValueDefinition>]) {
+  list(
+    printer,
+    Token::ParenOpen,
+    Token::ParenClose,
+    arguments,
+    print_input_value_definition,
+  );
+}
+
+fn print_input_fields(printer: &mut Printer, fields: &[Node<InputValueDefinition>]) {
+  list(
+    printer,
+    Token::BraceOpen,
+    Token::BraceClose,
+    fields,
+    print_input_value_definition,
+  );
+}
+
+fn print_input_value_definition(printer: &mut Printer, definition: &Node<InputValueDefinition>) {
+  print_description(printer, &definition.description);
+  name(printer, &definition.name);
+  printer.push(Token::Colon);
+  print_type(printer, &definition.ty);
+  if let Some(default_value) = &definition.default_value {
+    printer.push(Token::Equals);
+    print_value(printer, default_value);
+  }
+  print_directives(printer, &definition.directives);
+}
+
+fn print_enum_values(printer: &mut Printer, values: &[Node<EnumValueDefinition>]) {
+  list(
+    printer,
+    Token::BraceOpen,
+    Token::BraceClose,
+    values,
+    |printer, value| {
+      print_description(printer, &value.description);
+      name(printer, &value.value);
+      print_directives(printer, &value.directives);
+    },
+  );
+}
+
+fn print_variable_definitions(printer: &mut Printer, variables: &[Node<VariableDefinition>]) {
+  list(
+    printer,
+    Token::ParenOpen,
+    Token::ParenClose,
+    variables,
+    |printer, definition| {
+      variable(printer, &definition.name);
+      printer.push(Token::Colon);
+      print_type(printer, &definition.ty);
+      if let Some(default_value) = &definition.default_value {
+        printer.push(Token::Equals);
+        print_value(printer, default_value);
+      }
+      print_directives(printer, &definition.directives);
+    },
+  );
+}
+
+fn print_selection_set(printer: &mut Printer, selection_set: &[Selection]) {
+  list(
+    printer,
+    Token::BraceOpen,
+    Token::BraceClose,
+    selection_set,
+    |printer, selection| match selection {
+      Selection::Field(field) => {
+        if let Some(alias) = &field.alias {
+          name(printer, alias);
+          printer.push(Token::Colon);
+        }
+        name(printer, &field.name);
+        print_arguments(printer, &field.arguments);
+        print_directives(printer, &field.directives);
+        print_selection_set(printer, &field.selection_set);
+      }
+      Selection::FragmentSpread(spread) => {
+        printer.push(Token::Ellipsis);
+        name(printer, &spread.fragment_name);
+        print_directives(printer, &spread.directives);
+      }
+      Selection::InlineFragment(fragment) => {
+        printer.push(Token::Ellipsis);
+        if let Some(type_condition) = &fragment.type_condition {
+          name(printer, "on");
+          name(printer, type_condition);
+        }
+        print_directives(printer, &fragment.directives);
+        print_selection_set(printer, &fragment.selection_set);
+      }
+    },
+  );
+}
+
+fn print_directives(printer: &mut Printer, directives: &DirectiveList) {
+  for value in directives.iter() {
+    directive(printer, &value.name);
+    print_arguments(printer, &value.arguments);
+  }
+}
+
+fn print_arguments(printer: &mut Printer, arguments: &[Node<Argument>]) {
+  list(
+    printer,
+    Token::ParenOpen,
+    Token::ParenClose,
+    arguments,
+    |printer, argument| {
+      name(printer, &argument.name);
+      printer.push(Token::Colon);
+      print_value(printer, &argument.value);
+    },
+  );
+}
+
+fn print_type(printer: &mut Printer, value: &Type) {
+  match value {
+    Type::Named(type_name) => name(printer, type_name),
+    Type::NonNullNamed(type_name) => {
+      name(printer, type_name);
+      printer.push(Token::Exclamation);
+    }
+    Type::List(item) | Type::NonNullList(item) => {
+      printer.push(Token::BracketOpen);
+      print_type(printer, item);
+      printer.push(Token::BracketClose);
+      if matches!(value, Type::NonNullList(_)) {
+        printer.push(Token::Exclamation);
+      }
+    }
+  }
+}
+
+fn print_value(printer: &mut Printer, value: &Value) {
+  match value {
+    Value::Null => name(printer, "null"),
+    Value::Enum(value) => name(printer, value),
+    Value::Variable(name) => variable(printer, name),
+    Value::String(value) => string(printer, value),
+    Value::Float(number) => printer.push(Token::Float(number.as_str())),
+    Value::Int(number) => printer.push(Token::Int(number.as_str())),
+    Value::Boolean(true) => printer.push(Token::Bool("true")),
+    Value::Boolean(false) => printer.push(Token::Bool("false")),
+    Value::List(items) => {
+      printer.push(Token::BracketOpen);
+      for item in items {
+        print_value(printer, item);
+      }
+      printer.push(Token::BracketClose);
+    }
+    Value::Object(fields) => {
+      printer.push(Token::BraceOpen);
+      for (field, value) in fields {
+        name(printer, field);
+        printer.push(Token::Colon);
+        print_value(printer, value);
+      }
+      printer.push(Token::BraceClose);
+    }
+  }
+}
+
+/// Prints `items` separated by `separator`, e.g. `A & B` or `A | B`.
+fn separated<T>(
+  printer: &mut Printer,
+  separator: Token,
+  items: &[T],
+  mut item: impl FnMut(&mut Printer, &T),
+) {
+  for (i, value) in items.iter().enumerate() {
+    if i > 0 {
+      printer.push(separator.clone());
+    }
+    item(printer, value);
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{minify, minify_ast};
+  use apollo_compiler::ast::Document;
+
+  #[test]
+  fn prints_the_same_as_minify() {
+    let query = r#"
+      query SomeQuery($foo: String! = "default", $bar: [Int!]! @onVariable) @onQuery {
+        alias: someField(foo: $foo, bar: $bar, list: [1, 2.5, -3], obj: {a: true, b: null}) {
+          ...fragmented @onSpread
+          ... on User @include(if: $foo) { id }
+          ... { name }
+        }
+      }
+
+      mutation { like(story: 123) { story { id } } }
+
+      fragment fragmented on Foo @onFragment { bar }
+
+      { unnamed }
+    "#;
+    let document = Document::parse(query, "query.graphql").unwrap();
+
+    assert_eq!(minify_ast(&document), minify(query).unwrap());
+  }
+
+  #[test]
+  fn prints_type_system_documents() {
+    let schema = r#"
+      schema @onSchema { query: Query mutation: Mutation }
+
+      "Description"
+      type Query implements Node & Entity @onObject {
+        "Field description" field(arg: [String!] = ["a"] @onArgument): String! @deprecated
+        id: ID!
+      }
+
+      interface Node implements Entity { id: ID! }
+      union Result @onUnion = Query | Node
+      enum Kind { "Value description" FAST SLOW @deprecated }
+      input Filter { kind: Kind = FAST, "Input description" limit: Int }
+      scalar Date @specifiedBy(url: "https://example.com")
+      directive @onObject(reason: String) repeatable on OBJECT | INTERFACE
+
+      extend schema @extended
+      extend type Query implements Other { extra: Int }
+      extend interface Node @extended
+      extend union Result = Other
+      extend enum Kind { MEDIUM }
+      extend input Filter { offset: Int }
+      extend scalar Date @extended
+    "#;
+    let document = Document::parse(schema, "schema.graphql").unwrap();
+
+    assert_eq!(minify_ast(&document), minify(schema).unwrap());
+  }
+
+  #[test]
+  fn prints_descriptions_as_regular_strings() {
+    let document = Document::parse(
+      "\"\"\"\n  Multi-line\n  \"description\"\n\"\"\"\nscalar Date",
+      "schema.graphql",
+    )
+    .unwrap();
+
+    assert_eq!(
+      minify_ast(&document),
+      r#""Multi-line\n\"description\"" scalar Date"#
+    );
+  }
+}
@@ -0,0 +1,266 @@
+use super::{directive, list, name, string, variable, MinifyAst};
+use crate::lexer::Token;
+use crate::printer::Printer;
+use graphql_parser::query::{
+  Definition, Directive, Document, OperationDefinition, Selection, SelectionSet, Text, Type,
+  TypeCondition, Value, VariableDefinition,
+};
+
+impl<'a, T: Text<'a>> MinifyAst for Document<'a, T> {
+  fn minify_ast(&self) -> String {
+    let mut printer = Printer::default();
+
+    for definition in &self.definitions {
+      print_definition(&mut printer, definition);
+    }
+
+    printer.finish()
+  }
+}
+
+fn print_definition<'a, T: Text<'a>>(printer: &mut Printer, definition: &Definition<'a, T>) {
+  match definition {
+    Definition::Operation(OperationDefinition::SelectionSet(selection_set)) => {
+      print_selection_set(printer, selection_set);
+    }
+    Definition::Operation(OperationDefinition::Query(query)) => print_operation(
+      printer,
+      "query",
+      query.name.as_ref(),
+      &query.variable_definitions,
+      &query.directives,
+      &query.selection_set,
+    ),
+    Definition::Operation(OperationDefinition::Mutation(mutation)) => print_operation(
+      printer,
+      "mutation",
+      mutation.name.as_ref(),
+      &mutation.variable_definitions,
+      &mutation.directives,
+      &mutation.selection_set,
+    ),
+    Definition::Operation(OperationDefinition::Subscription(subscription)) => print_operation(
+      printer,
+      "subscription",
+      subscription.name.as_ref(),
+      &subscription.variable_definitions,
+      &subscription.directives,
+      &subscription.selection_set,
+    ),
+    Definition::Fragment(fragment) => {
+      name(printer, "fragment");
+      name(printer, fragment.name.as_ref());
+      print_type_condition(printer, &fragment.type_condition);
+      print_directives(printer, &fragment.directives);
+      print_selection_set(printer, &fragment.selection_set);
+    }
+  }
+}
+
+fn print_operation<'a, T: Text<'a>>(
+  printer: &mut Printer,
+  kind: &str,
+  operation_name: Option<&T::Value>,
+  variable_definitions: &[VariableDefinition<'a, T>],
+  directives: &[Directive<'a, T>],
+  selection_set: &SelectionSet<'a, T>,
+) {
+  name(printer, kind);
+  if let Some(operation_name) = operation_name {
+    name(printer, operation_name.as_ref());
+  }
+  list(
+    printer,
+    Token::ParenOpen,
+    Token::ParenClose,
+    variable_definitions,
+    |printer, definition| {
+      variable(printer, definition.name.as_ref());
+      printer.push(Token::Colon);
+      print_type(printer, &definition.var_type);
+      if let Some(default_value) = &definition.default_value {
+        printer.push(Token::Equals);
+        print_value(printer, default_value);
+      }
+    },
+  );
+  print_directives(printer, directives);
+  print_selection_set(printer, selection_set);
+}
+
+fn print_selection_set<'a, T: Text<'a>>(
+  printer: &mut Printer,
+  selection_set: &SelectionSet<'a, T>,
+) {
+  list(
+    printer,
+    Token::BraceOpen,
+    Token::BraceClose,
+    &selection_set.items,
+    |printer, selection| match selection {
+      Selection::Field(field) => {
+        if let Some(alias) = &field.alias {
+          name(printer, alias.as_ref());
+          printer.push(Token::Colon);
+        }
+        name(printer, field.name.as_ref());
+        print_arguments(printer, &field.arguments);
+        print_directives(printer, &field.directives);
+        print_selection_set(printer, &field.selection_set);
+      }
+      Selection::FragmentSpread(spread) => {
+        printer.push(Token::Ellipsis);
+        name(printer, spread.fragment_name.as_ref());
+        print_directives(printer, &spread.directives);
+      }
+      Selection::InlineFragment(fragment) => {
+        printer.push(Token::Ellipsis);
+        if let Some(type_condition) = &fragment.type_condition {
+          print_type_condition(printer, type_condition);
+        }
+        print_directives(printer, &fragment.directives);
+        print_selection_set(printer, &fragment.selection_set);
+      }
+    },
+  );
+}
+
+fn print_type_condition<'a, T: Text<'a>>(printer: &mut Printer, condition: &TypeCondition<'a, T>) {
+  let TypeCondition::On(type_name) = condition;
+  name(printer, "on");
+  name(printer, type_name.as_ref());
+}
+
+fn print_directives<'a, T: Text<'a>>(printer: &mut Printer, directives: &[Directive<'a, T>]) {
+  for value in directives {
+    directive(printer, value.name.as_ref());
+    print_arguments(printer, &value.arguments);
+  }
+}
+
+fn print_arguments<'a, T: Text<'a>>(printer: &mut Printer, arguments: &[(T::Value, Value<'a, T>)]) {
+  list(
+    printer,
+    Token::ParenOpen,
+    Token::ParenClose,
+    arguments,
+    |printer, (argument, value)| {
+      name(printer, argument.as_ref());
+      printer.push(Token::Colon);
+      print_value(printer, value);
+    },
+  );
+}
+
+fn print_type<'a, T: Text<'a>>(printer: &mut Printer, value: &Type<'a, T>) {
+  match value {
+    Type::NamedType(type_name) => name(printer, type_name.as_ref()),
+    Type::ListType(item) => {
+      printer.push(Token::BracketOpen);
+      print_type(printer, item);
+      printer.push(Token::BracketClose);
+    }
+    Type::NonNullType(item) => {
+      print_type(printer, item);
+      printer.push(Token::Exclamation);
+    }
+  }
+}
+
+fn float(printer: &mut Printer, number: f64) {
+  match number {
+    // Numbers too large for `f64`, like `1e400`, are parsed as infinity. Print the shortest float
+    // that is parsed as infinity again, as `Debug` would print `inf`.
+    f64::INFINITY => printer.push(Token::Float("1e309")),
+    f64::NEG_INFINITY => printer.push(Token::Float("-1e309")),
+    // Never produced by the parser and there is no literal for it.
+    _ if number.is_nan() => name(printer, "null"),
+    // `Debug` keeps the fractional part of whole numbers, which `Display` would drop.
+    _ => printer.push(Token::Float(&format!("{number:?}"))),
+  }
+}
+
+fn print_value<'a, T: Text<'a>>(printer: &mut Printer, value: &Value<'a, T>) {
+  match value {
+    Value::Variable(name) => variable(printer, name.as_ref()),
+    Value::Int(number) => {
+      printer.push(Token::Int(&number.as_i64().unwrap_or_default().to_string()))
+    }
+    Value::Float(number) => float(printer, *number),
+    Value::String(value) => string(printer, value),
+    Value::Boolean(true) => printer.push(Token::Bool("true")),
+    Value::Boolean(false) => printer.push(Token::Bool("false")),
+    Value::Null => name(printer, "null"),
+    Value::Enum(value) => name(printer, value.as_ref()),
+    Value::List(items) => {
+      printer.push(Token::BracketOpen);
+      for item in items {
+        print_value(printer, item);
+      }
+      printer.push(Token::BracketClose);
+    }
+    Value::Object(fields) => {
+      printer.push(Token::BraceOpen);
+      for (field, value) in fields {
+        name(printer, field.as_ref());
+        printer.push(Token::Colon);
+        print_value(printer, value);
+      }
+      printer.push(Token::BraceClose);
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{minify, minify_ast};
+  use graphql_parser::parse_query;
+
+  #[test]
+  fn prints_the_same_as_minify() {
+    let query = r#"
+      query SomeQuery($foo: String! = "default", $bar: [Int!]) @onQuery {
+        alias: someField(foo: $foo, bar: $bar, list: [1, 2.5, -3], obj: {a: true, b: null}) {
+          ...fragmented @onSpread
+          ... on User @include(if: $foo) { id }
+          ... { name }
+        }
+      }
+
+      mutation { like(story: 123) { story { id } } }
+
+      subscription OnLike { liked(kind: FAST) }
+
+      fragment fragmented on Foo @onFragment { bar }
+
+      { unnamed }
+    "#;
+    let document = parse_query::<&str>(query).unwrap();
+
+    assert_eq!(minify_ast(&document), minify(query).unwrap());
+  }
+
+  #[test]
+  fn prints_strings_and_floats_in_their_shortest_valid_form() {
+    let document =
+      parse_query::<String>("{ a(b: \"\"\"\n  line\n  \"quoted\"\n\"\"\", c: 1.0, d: 1e3) }")
+        .unwrap();
+
+    assert_eq!(
+      minify_ast(&document),
+      r#"{a(b:"line\n\"quoted\"" c:1.0 d:1000.0)}"#
+    );
+  }
+
+  #[test]
+  fn prints_out_of_range_floats_as_infinity() {
+    let document = parse_query::<String>("{ a(b: 1e400, c: -1e400) }").unwrap();
+    let minified = minify_ast(&document);
+
+    assert_eq!(minified, "{a(b:1e309 c:-1e309)}");
+    assert_eq!(
+      minify_ast(&parse_query::<String>(&minified).unwrap()),
+      minified
+    );
+  }
+}
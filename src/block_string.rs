@@ -21,6 +21,41 @@ pub(crate) enum BlockStringToken {
   TripleQuote,
 }
 
+/// Prints the block string token `raw`, including its delimiters, in its minified form.
+pub(crate) fn minify_block_string(raw: &str) -> String {
+  print_block_string(block_string_value(raw))
+}
+
+/// Returns the value of the block string token `raw`, including its delimiters, with the common
+/// indentation and leading and trailing blank lines removed.
+pub(crate) fn block_string_value(raw: &str) -> String {
+  let mut lines = vec![];
+  let mut current_line = String::new();
+  let content = raw.strip_prefix(r#"""""#).unwrap_or(raw);
+  let mut block_lexer = BlockStringToken::lexer(content);
+
+  while let Some(Ok(token)) = block_lexer.next() {
+    match token {
+      BlockStringToken::NewLine => {
+        lines.push(current_line);
+        current_line = String::new();
+      }
+      BlockStringToken::Text | BlockStringToken::Quote | BlockStringToken::EscapeSeq => {
+        current_line.push_str(block_lexer.slice())
+      }
+      BlockStringToken::EscapedTripleQuote => current_line.push_str(r#"""""#),
+      BlockStringToken::TripleQuote => break,
+    }
+  }
+
+  if !current_line.is_empty() {
+    lines.push(current_line);
+  }
+
+  dedent_block_lines_mut(&mut lines);
+  lines.join("\n")
+}
+
 pub(crate) fn print_block_string<T: AsRef<str>>(input: T) -> String {
   let str = input.as_ref();
   let str = str.replace(r#"""""#, r#"\""""#);
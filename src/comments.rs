@@ -0,0 +1,77 @@
+use crate::lexer::{Lexer, LexingError, Token};
+use std::ops::Range;
+
+/// Extracts the `#` comments of a GraphQL document, in the order they appear.
+///
+/// Each comment is returned with its byte range in the document, which includes the `#`, and its
+/// text without the `#`. Comments are found by the same lexer that [`minify`](crate::minify) uses,
+/// so a `#` inside a string is never mistaken for one.
+///
+/// # Examples
+///
+/// ```
+/// use graphql_minify::extract_comments;
+///
+/// let document = r##"
+/// ## Fetches the viewer
+/// query Viewer {
+///   viewer { name(suffix: "#1") } # the name only
+/// }
+/// "##;
+/// let comments = extract_comments(document).unwrap();
+///
+/// assert_eq!(comments[0].1, " Fetches the viewer");
+/// assert_eq!(&document[comments[1].0.clone()], "# the name only");
+/// assert_eq!(comments.len(), 2);
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if the lexing process encounters an unexpected character.
+pub fn extract_comments(source: &str) -> Result<Vec<(Range<usize>, &str)>, LexingError> {
+  let mut lexer = Lexer::new(source);
+  let mut comments = vec![];
+
+  while let Some(token) = lexer.next() {
+    if let Token::Comment(comment) = token? {
+      comments.push((lexer.span(), &comment[1..]));
+    }
+  }
+
+  Ok(comments)
+}
+
+#[cfg(test)]
+mod test {
+  use super::extract_comments;
+  use crate::lexer::LexingError;
+
+  #[test]
+  fn extracts_comments_with_their_ranges() {
+    let document = "#a\r\n{ b #c\n  d}#\n# e ";
+
+    assert_eq!(
+      extract_comments(document).unwrap(),
+      [(0..2, "a"), (8..10, "c"), (15..16, ""), (17..21, " e ")]
+    );
+  }
+
+  #[test]
+  fn ignores_hashes_in_strings_and_block_strings() {
+    let document = r##"{ a(b: "#c", d: """#e
+      #f""") } #g"##;
+
+    assert_eq!(
+      extract_comments(document).unwrap(),
+      [(document.len() - 2..document.len(), "g")]
+    );
+  }
+
+  #[test]
+  fn errs_on_invalid_token() {
+    assert_eq!(
+      extract_comments("# a\n%"),
+      Err(LexingError::UnknownToken(4))
+    );
+  }
+}
@@ -0,0 +1,115 @@
+use crate::lexer::LexingError;
+use crate::minify;
+use std::fmt;
+use std::ops::Deref;
+
+/// A GraphQL document that is minified by construction.
+///
+/// With the `serde` feature enabled it deserializes from a string, minifying it on the way and
+/// rejecting documents that can not be minified, and serializes as the minified string.
+///
+/// # Examples
+///
+/// ```
+/// use graphql_minify::MinifiedDocument;
+///
+/// let document = MinifiedDocument::new("query { user { id name } }").unwrap();
+///
+/// assert_eq!(&*document, "query{user{id name}}");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MinifiedDocument(String);
+
+impl MinifiedDocument {
+  /// Minifies `value`, see [`minify`](crate::minify).
+  ///
+  /// # Errors
+  ///
+  /// This function will return an error if the lexing process encounters an unexpected character.
+  pub fn new<T: AsRef<str>>(value: T) -> Result<Self, LexingError> {
+    minify(value).map(MinifiedDocument)
+  }
+
+  pub fn as_str(&self) -> &str {
+    &self.0
+  }
+
+  pub fn into_string(self) -> String {
+    self.0
+  }
+}
+
+impl Deref for MinifiedDocument {
+  type Target = str;
+
+  fn deref(&self) -> &str {
+    &self.0
+  }
+}
+
+impl AsRef<str> for MinifiedDocument {
+  fn as_ref(&self) -> &str {
+    &self.0
+  }
+}
+
+impl fmt::Display for MinifiedDocument {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(&self.0)
+  }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for MinifiedDocument {
+  fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&self.0)
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for MinifiedDocument {
+  fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    struct DocumentVisitor;
+
+    impl<'de> serde::de::Visitor<'de> for DocumentVisitor {
+      type Value = MinifiedDocument;
+
+      fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a GraphQL document")
+      }
+
+      fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        MinifiedDocument::new(value).map_err(E::custom)
+      }
+    }
+
+    deserializer.deserialize_str(DocumentVisitor)
+  }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+  use super::MinifiedDocument;
+
+  #[test]
+  fn minifies_on_deserialize() {
+    let document: MinifiedDocument =
+      serde_json::from_str(r#""query {\n  user { id name }\n}""#).unwrap();
+
+    assert_eq!(document.as_str(), "query{user{id name}}");
+  }
+
+  #[test]
+  fn rejects_documents_that_can_not_be_minified() {
+    let error = serde_json::from_str::<MinifiedDocument>(r#""{ foo(arg: %) }""#).unwrap_err();
+
+    assert!(error.to_string().starts_with("unknown token at offset 11"));
+  }
+
+  #[test]
+  fn serializes_as_the_minified_string() {
+    let document = MinifiedDocument::new("{ a, b }").unwrap();
+
+    assert_eq!(serde_json::to_string(&document).unwrap(), r#""{a b}""#);
+  }
+}
@@ -0,0 +1,171 @@
+//! C bindings, enabled with the `ffi` feature.
+//!
+//! The matching header is `include/graphql_minify.h`, generated with
+//! `cbindgen --config cbindgen.toml --output include/graphql_minify.h`. Build a linkable library with
+//! `cargo rustc --release --features ffi --crate-type cdylib` (or `staticlib`).
+
+use crate::lexer::LexingError;
+use crate::minify;
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+/// Outcome of [`graphql_minify_minify`].
+#[repr(C)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum GraphQLMinifyStatus {
+  Ok = 0,
+  UnknownToken = 1,
+  UnterminatedString = 2,
+  InvalidUtf8 = 3,
+  NullPointer = 4,
+  InvalidEscapeSequence = 5,
+  InvalidNumber = 6,
+}
+
+/// Filled in by [`graphql_minify_minify`] and released with [`graphql_minify_result_free`].
+///
+/// On success `minified` points to a NUL-terminated string of `minified_len` bytes owned by this
+/// library. Otherwise `minified` is null and `error_offset` is the byte offset of the error, with
+/// `error_line` and `error_column` being its one-based position.
+#[repr(C)]
+#[derive(Debug)]
+pub struct GraphQLMinifyResult {
+  pub minified: *mut c_char,
+  pub minified_len: usize,
+  pub error_offset: usize,
+  pub error_line: usize,
+  pub error_column: usize,
+}
+
+/// Minifies the NUL-terminated, UTF-8 encoded document `query` into `result`.
+///
+/// # Safety
+///
+/// `query` must be null or point to a NUL-terminated string, `result` must be null or point to
+/// writable memory for a [`GraphQLMinifyResult`]. Every result written by this function has to be
+/// released with [`graphql_minify_result_free`].
+#[no_mangle]
+pub unsafe extern "C" fn graphql_minify_minify(
+  query: *const c_char,
+  result: *mut GraphQLMinifyResult,
+) -> GraphQLMinifyStatus {
+  if query.is_null() || result.is_null() {
+    return GraphQLMinifyStatus::NullPointer;
+  }
+
+  let mut output = GraphQLMinifyResult {
+    minified: ptr::null_mut(),
+    minified_len: 0,
+    error_offset: 0,
+    error_line: 0,
+    error_column: 0,
+  };
+
+  let status = match CStr::from_ptr(query).to_str() {
+    Ok(query) => match minify(query) {
+      Ok(minified) => {
+        // A document read from a C string can not contain NUL, and neither can its minified form.
+        let minified = CString::new(minified).expect("minified document contains NUL");
+        output.minified_len = minified.as_bytes().len();
+        output.minified = minified.into_raw();
+        GraphQLMinifyStatus::Ok
+      }
+      Err(e) => {
+        (output.error_line, output.error_column) = e.location(query);
+        output.error_offset = e.offset();
+        match e {
+          LexingError::UnknownToken(_) => GraphQLMinifyStatus::UnknownToken,
+          LexingError::UnterminatedString(_) => GraphQLMinifyStatus::UnterminatedString,
+          LexingError::InvalidEscapeSequence(_) => GraphQLMinifyStatus::InvalidEscapeSequence,
+          LexingError::InvalidNumber(_) => GraphQLMinifyStatus::InvalidNumber,
+        }
+      }
+    },
+    Err(e) => {
+      output.error_offset = e.valid_up_to();
+      GraphQLMinifyStatus::InvalidUtf8
+    }
+  };
+
+  result.write(output);
+  status
+}
+
+/// Releases the string owned by `result` and resets it, so freeing a result twice is harmless.
+///
+/// # Safety
+///
+/// `result` must be null or point to a [`GraphQLMinifyResult`] written by [`graphql_minify_minify`].
+#[no_mangle]
+pub unsafe extern "C" fn graphql_minify_result_free(result: *mut GraphQLMinifyResult) {
+  if let Some(result) = result.as_mut() {
+    if !result.minified.is_null() {
+      drop(CString::from_raw(result.minified));
+    }
+    result.minified = ptr::null_mut();
+    result.minified_len = 0;
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::{
+    graphql_minify_minify, graphql_minify_result_free, GraphQLMinifyResult, GraphQLMinifyStatus,
+  };
+  use std::ffi::{CStr, CString};
+  use std::mem::MaybeUninit;
+  use std::ptr;
+
+  fn minify_c(query: &[u8]) -> (GraphQLMinifyStatus, GraphQLMinifyResult) {
+    let query = CString::new(query).unwrap();
+    let mut result = MaybeUninit::uninit();
+
+    unsafe {
+      let status = graphql_minify_minify(query.as_ptr(), result.as_mut_ptr());
+      (status, result.assume_init())
+    }
+  }
+
+  #[test]
+  fn minifies_c_strings() {
+    let (status, mut result) = minify_c(b"query { user { id name } }");
+
+    assert_eq!(status, GraphQLMinifyStatus::Ok);
+    assert_eq!(result.minified_len, 20);
+    assert_eq!(
+      unsafe { CStr::from_ptr(result.minified) }.to_str(),
+      Ok("query{user{id name}}")
+    );
+
+    unsafe {
+      graphql_minify_result_free(&mut result);
+      graphql_minify_result_free(&mut result);
+    }
+    assert!(result.minified.is_null());
+  }
+
+  #[test]
+  fn reports_error_positions() {
+    let (status, result) = minify_c(b"{\n  foo(arg: %)\n}");
+
+    assert_eq!(status, GraphQLMinifyStatus::UnknownToken);
+    assert!(result.minified.is_null());
+    assert_eq!(
+      (result.error_offset, result.error_line, result.error_column),
+      (13, 2, 12)
+    );
+  }
+
+  #[test]
+  fn rejects_invalid_input() {
+    let (status, result) = minify_c(b"{ foo\xff }");
+
+    assert_eq!(status, GraphQLMinifyStatus::InvalidUtf8);
+    assert_eq!(result.error_offset, 5);
+
+    let mut result = MaybeUninit::uninit();
+    let status = unsafe { graphql_minify_minify(ptr::null(), result.as_mut_ptr()) };
+
+    assert_eq!(status, GraphQLMinifyStatus::NullPointer);
+  }
+}
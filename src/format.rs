@@ -0,0 +1,448 @@
+use crate::block_string::block_string_value;
+use crate::lexer::{expects_name, is_definition_keyword, Lexer, LexingError, Token};
+use std::ops::Range;
+
+/// Pretty-prints a GraphQL document in the layout of GraphQL.js' `print`: two spaces of
+/// indentation, one selection or field per line, `, ` between arguments and list or object values,
+/// and a blank line between definitions.
+///
+/// Unlike `print`, the document is not parsed, so comments are kept and arguments are never
+/// wrapped onto multiple lines. Minifying the output gives the same result as minifying the input.
+///
+/// # Examples
+///
+/// ```
+/// use graphql_minify::format;
+///
+/// let formatted = format("query Q($a:Int){user(id:$a){...on User{name}}}").unwrap();
+///
+/// assert_eq!(
+///   formatted,
+///   "query Q($a: Int) {\n  user(id: $a) {\n    ... on User {\n      name\n    }\n  }\n}"
+/// );
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if the lexing process encounters an unexpected character.
+pub fn format<T: AsRef<str>>(value: T) -> Result<String, LexingError> {
+  let source = value.as_ref();
+  let mut lexer = Lexer::new(source);
+  let mut tokens = vec![];
+
+  while let Some(token) = lexer.next() {
+    tokens.push((token?, lexer.span()));
+  }
+
+  let mut formatter = Formatter::default();
+  for (i, (token, span)) in tokens.iter().enumerate() {
+    match token {
+      Token::Comment(_) => {
+        let own_line = source[formatter.last_end..span.start].contains(['\n', '\r']);
+        let next = tokens[i + 1..]
+          .iter()
+          .find(|(token, _)| !matches!(token, Token::Comment(_)));
+        formatter.push_comment(token, own_line, next.map(|(token, _)| token));
+      }
+      _ => formatter.push(token, span),
+    }
+  }
+
+  Ok(formatter.result)
+}
+
+/// What the innermost open bracket contains.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Context {
+  /// A selection set or the body of a type system definition, one item per line.
+  Block,
+  /// Arguments, variable or argument definitions, separated by `, `.
+  Arguments,
+  /// A list or object value, separated by `, `.
+  Value,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Separator {
+  None,
+  Space,
+  Comma,
+  Newline,
+  BlankLine,
+}
+
+#[derive(Debug, Default)]
+struct Formatter<'a> {
+  result: String,
+  stack: Vec<Context>,
+  last_token: Option<Token<'a>>,
+  before_last_token: Option<Token<'a>>,
+  last_end: usize,
+  /// Whether the last token is the first of an argument, i.e. possibly its description.
+  last_began_item: bool,
+  /// Whether the next definition at the top level opens with a brace, as opposed to a shorthand
+  /// query following a definition without a body.
+  expects_body: bool,
+  /// Whether a comment requires the next token to start on a new line.
+  after_comment: bool,
+  /// Whether the next definition is already separated by a blank line before its comments.
+  definition_separated: bool,
+}
+
+impl<'a> Formatter<'a> {
+  fn push(&mut self, token: &Token<'a>, span: &Range<usize>) {
+    let closed = match token {
+      Token::BraceClose | Token::ParenClose | Token::BracketClose => self.stack.pop(),
+      _ => None,
+    };
+    let mut separator = match closed {
+      Some(Context::Block) => Separator::Newline,
+      Some(_) => Separator::None,
+      None => self.separator(token),
+    };
+
+    if self.after_comment && separator != Separator::BlankLine {
+      separator = Separator::Newline;
+    }
+    if self.definition_separated && separator == Separator::BlankLine {
+      separator = Separator::Newline;
+    }
+    self.write_separator(separator);
+
+    match token {
+      Token::BlockString(raw) => self.write_block_string(raw),
+      _ => self.result.push_str(token.as_str()),
+    }
+
+    match token {
+      Token::BraceOpen if matches!(self.stack.last(), None | Some(Context::Block)) => {
+        self.stack.push(Context::Block)
+      }
+      Token::BraceOpen | Token::BracketOpen => self.stack.push(Context::Value),
+      Token::ParenOpen => self.stack.push(Context::Arguments),
+      Token::Identifier(keyword) if self.is_definition_keyword(keyword) => {
+        self.expects_body = !matches!(*keyword, "scalar" | "union" | "directive");
+      }
+      _ => {}
+    }
+
+    self.last_began_item =
+      matches!(separator, Separator::Comma) || matches!(self.last_token, Some(Token::ParenOpen));
+    self.before_last_token = self.last_token.replace(token.clone());
+    self.last_end = span.end;
+    self.after_comment = false;
+    self.definition_separated = false;
+  }
+
+  fn push_comment(&mut self, comment: &Token<'a>, own_line: bool, next: Option<&Token<'a>>) {
+    if self.result.is_empty() {
+      // Comments at the start of the document stay in place.
+    } else if own_line
+      && self.stack.is_empty()
+      && next.is_none_or(|next| self.starts_definition(next))
+    {
+      if !self.definition_separated {
+        self.write_separator(Separator::BlankLine);
+        self.definition_separated = true;
+      } else {
+        self.write_separator(Separator::Newline);
+      }
+    } else if own_line || self.after_comment {
+      self.write_separator(Separator::Newline);
+    } else {
+      self.write_separator(Separator::Space);
+    }
+
+    self.result.push_str(comment.as_str());
+    self.after_comment = true;
+  }
+
+  fn separator(&self, token: &Token<'a>) -> Separator {
+    let Some(last_token) = &self.last_token else {
+      return Separator::None;
+    };
+
+    match self.stack.last() {
+      None if self.starts_definition(token) => return Separator::BlankLine,
+      None if is_description(last_token) => return Separator::Newline,
+      Some(Context::Block) if *last_token == Token::BraceOpen => return Separator::Newline,
+      Some(Context::Block) if self.starts_block_item(token) => return Separator::Newline,
+      Some(Context::Arguments | Context::Value)
+        if matches!(
+          last_token,
+          Token::ParenOpen | Token::BracketOpen | Token::BraceOpen
+        ) =>
+      {
+        return Separator::None
+      }
+      Some(context @ (Context::Arguments | Context::Value))
+        if self.starts_inline_item(token, *context) =>
+      {
+        return Separator::Comma
+      }
+      _ => {}
+    }
+
+    match (last_token, token) {
+      (_, Token::Colon | Token::Exclamation | Token::Question) => Separator::None,
+      (Token::Identifier(_), Token::BracketOpen) if self.stack.last() == Some(&Context::Block) => {
+        Separator::None
+      }
+      (Token::Identifier("query" | "mutation" | "subscription"), Token::ParenOpen)
+        if self.stack.is_empty() =>
+      {
+        Separator::Space
+      }
+      (_, Token::ParenOpen) => Separator::None,
+      (Token::Ellipsis, Token::Identifier("on") | Token::Directive(_) | Token::BraceOpen) => {
+        Separator::Space
+      }
+      (Token::Ellipsis, _) => Separator::None,
+      _ => Separator::Space,
+    }
+  }
+
+  /// Whether `token` at the top level begins a new definition.
+  fn starts_definition(&self, token: &Token<'a>) -> bool {
+    if !self.stack.is_empty() {
+      return false;
+    }
+    let Some(last_token) = &self.last_token else {
+      return true;
+    };
+
+    match token {
+      _ if *last_token == Token::BraceClose => true,
+      Token::BraceOpen => !self.expects_body,
+      Token::String(_) | Token::BlockString(_) => true,
+      Token::Identifier(keyword) => {
+        self.is_definition_keyword(keyword)
+          && !is_description(last_token)
+          && *last_token != Token::Identifier("extend")
+      }
+      _ => false,
+    }
+  }
+
+  /// Whether `keyword` at the top level is the keyword of a definition rather than a name.
+  fn is_definition_keyword(&self, keyword: &str) -> bool {
+    self.stack.is_empty()
+      && (is_definition_keyword(keyword) || keyword == "extend")
+      && !self.last_token.as_ref().is_some_and(expects_name)
+  }
+
+  /// Whether `token` in a selection set or type system definition body begins a new item.
+  fn starts_block_item(&self, token: &Token<'a>) -> bool {
+    let Some(last_token) = &self.last_token else {
+      return false;
+    };
+
+    matches!(
+      token,
+      Token::Identifier(_)
+        | Token::Bool(_)
+        | Token::Ellipsis
+        | Token::String(_)
+        | Token::BlockString(_)
+    ) && !matches!(last_token, Token::Colon | Token::Equals | Token::Ellipsis)
+      && !(*last_token == Token::Identifier("on")
+        && self.before_last_token == Some(Token::Ellipsis))
+  }
+
+  /// Whether `token` in arguments or a list or object value begins a new item.
+  fn starts_inline_item(&self, token: &Token<'a>, context: Context) -> bool {
+    let Some(last_token) = &self.last_token else {
+      return false;
+    };
+    let described =
+      context == Context::Arguments && self.last_began_item && is_description(last_token);
+
+    !described
+      && is_value_start(token)
+      && (is_value_end(last_token)
+        || matches!(last_token, Token::Exclamation | Token::Directive(_)))
+  }
+
+  fn write_separator(&mut self, separator: Separator) {
+    if self.result.is_empty() {
+      return;
+    }
+
+    match separator {
+      Separator::None => {}
+      Separator::Space if self.result.ends_with(['\n', ' ']) => {}
+      Separator::Space => self.result.push(' '),
+      Separator::Comma => self.result.push_str(", "),
+      Separator::Newline | Separator::BlankLine => {
+        self
+          .result
+          .truncate(self.result.trim_end_matches(' ').len());
+        if separator == Separator::BlankLine {
+          self.result.push('\n');
+        }
+        self.result.push('\n');
+        self.write_indentation();
+      }
+    }
+  }
+
+  fn write_indentation(&mut self) {
+    for _ in 0..self.stack.len() {
+      self.result.push_str("  ");
+    }
+  }
+
+  /// Writes a block string on its own lines at the current indentation, unless its value fits on
+  /// a single line.
+  fn write_block_string(&mut self, raw: &str) {
+    let value = block_string_value(raw).replace(r#"""""#, r#"\""""#);
+
+    if !value.contains('\n') && !value.ends_with(['"', '\\']) {
+      self.result.push_str(r#"""""#);
+      self.result.push_str(&value);
+      self.result.push_str(r#"""""#);
+      return;
+    }
+
+    self.result.push_str(r#"""""#);
+    for line in value.split('\n') {
+      self.result.push('\n');
+      if !line.is_empty() {
+        self.write_indentation();
+        self.result.push_str(line);
+      }
+    }
+    self.result.push('\n');
+    self.write_indentation();
+    self.result.push_str(r#"""""#);
+  }
+}
+
+fn is_description(token: &Token) -> bool {
+  matches!(token, Token::String(_) | Token::BlockString(_))
+}
+
+fn is_value_start(token: &Token) -> bool {
+  matches!(
+    token,
+    Token::Identifier(_)
+      | Token::Variable(_)
+      | Token::String(_)
+      | Token::BlockString(_)
+      | Token::Int(_)
+      | Token::Float(_)
+      | Token::Bool(_)
+      | Token::BracketOpen
+      | Token::BraceOpen
+  )
+}
+
+fn is_value_end(token: &Token) -> bool {
+  matches!(
+    token,
+    Token::Identifier(_)
+      | Token::Variable(_)
+      | Token::String(_)
+      | Token::BlockString(_)
+      | Token::Int(_)
+      | Token::Float(_)
+      | Token::Bool(_)
+      | Token::BracketClose
+      | Token::BraceClose
+      | Token::ParenClose
+  )
+}
+
+#[cfg(test)]
+mod test {
+  use super::format;
+  use crate::minify;
+
+  #[test]
+  fn formats_operations() {
+    let query = r#"query Q($a:Int=1,$b:[In!]!@d){a:b(c:$a,d:{e:[1 2],f:"g"})@skip(if:true){...F ...on T{h}... @include(if:$b){i}}}"#;
+
+    assert_eq!(
+      format(query).unwrap(),
+      r#"query Q($a: Int = 1, $b: [In!]! @d) {
+  a: b(c: $a, d: {e: [1, 2], f: "g"}) @skip(if: true) {
+    ...F
+    ... on T {
+      h
+    }
+    ... @include(if: $b) {
+      i
+    }
+  }
+}"#
+    );
+  }
+
+  #[test]
+  fn separates_definitions_by_blank_lines() {
+    let document = r#"query ($a: Int) { a } fragment F on query { b } { c } "A" type T implements I & J @d { "B" d(e: Int = 1 "F" f: [E!]): E! } union U = A | B scalar S { d }"#;
+
+    assert_eq!(
+      format(document).unwrap(),
+      r#"query ($a: Int) {
+  a
+}
+
+fragment F on query {
+  b
+}
+
+{
+  c
+}
+
+"A"
+type T implements I & J @d {
+  "B"
+  d(e: Int = 1, "F" f: [E!]): E!
+}
+
+union U = A | B
+
+scalar S
+
+{
+  d
+}"#
+    );
+  }
+
+  #[test]
+  fn indents_block_strings() {
+    let document =
+      "type T {\n\"\"\"\n    a\n      b\n  \"\"\"\nc(d: String = \"\"\"e\"\"\"): String\n}";
+
+    assert_eq!(
+      format(document).unwrap(),
+      "type T {\n  \"\"\"\n  a\n    b\n  \"\"\"\n  c(d: String = \"\"\"e\"\"\"): String\n}"
+    );
+  }
+
+  #[test]
+  fn keeps_comments() {
+    let document = "# a\nquery { b # c\n# d\ne }\n# f\ntype G { h }";
+
+    assert_eq!(
+      format(document).unwrap(),
+      "# a\nquery {\n  b # c\n  # d\n  e\n}\n\n# f\ntype G {\n  h\n}"
+    );
+  }
+
+  #[test]
+  fn minifies_to_the_same_document() {
+    let query = include_str!("../data/kitchen_sink_query.gql");
+    let schema = include_str!("../data/kitchen_sink_schema.gql");
+
+    for document in [query, schema] {
+      let formatted = format(document).unwrap();
+
+      assert_eq!(minify(&formatted).unwrap(), minify(document).unwrap());
+      assert_eq!(format(&formatted).unwrap(), formatted);
+    }
+  }
+}
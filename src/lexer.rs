@@ -1,19 +1,125 @@
-use crate::block_string::{dedent_block_lines_mut, print_block_string, BlockStringToken};
-use logos::{Lexer, Logos};
+use crate::block_string::BlockStringToken;
+use logos::Logos;
+use std::fmt;
+use std::ops::Range;
 
-#[derive(Debug, PartialEq, Clone, Default)]
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
 /// An enumeration of errors that can occur during the lexing process.
 pub enum LexingError {
-  #[default]
-  UnknownToken,
+  /// First value is the index of the first character that could not be lexed
+  UnknownToken(usize),
   /// First value is the index of the first character of the unterminated string
   UnterminatedString(usize),
+  /// First value is the index of the backslash starting the invalid escape sequence
+  InvalidEscapeSequence(usize),
+  /// First value is the index of the character that is not allowed in or directly after a number,
+  /// only reported when lexing strictly
+  InvalidNumber(usize),
 }
 
-#[derive(Logos, Debug, PartialEq)]
-#[logos(skip r"([\s,]+|#[^\r\n]*)+")]
+impl LexingError {
+  /// Byte offset into the document at which the error occurred.
+  pub fn offset(&self) -> usize {
+    match self {
+      LexingError::UnknownToken(offset)
+      | LexingError::UnterminatedString(offset)
+      | LexingError::InvalidEscapeSequence(offset)
+      | LexingError::InvalidNumber(offset) => *offset,
+    }
+  }
+
+  /// One-based line and column of the error in `source`, the document that failed to lex.
+  /// Columns are counted in characters, lines are terminated by `\n`, `\r\n` or `\r`. An offset
+  /// inside of a character is located at that character.
+  pub fn location(&self, source: &str) -> (usize, usize) {
+    let mut offset = self.offset().min(source.len());
+    while !source.is_char_boundary(offset) {
+      offset -= 1;
+    }
+    let before = &source[..offset];
+    let line_start = before.rfind(['\n', '\r']).map_or(0, |i| i + 1);
+    let line = before.matches(['\n', '\r']).count() - before.matches("\r\n").count() + 1;
+
+    (line, before[line_start..].chars().count() + 1)
+  }
+
+  pub(crate) fn shifted(self, by: usize) -> Self {
+    match self {
+      LexingError::UnknownToken(offset) => LexingError::UnknownToken(offset + by),
+      LexingError::UnterminatedString(offset) => LexingError::UnterminatedString(offset + by),
+      LexingError::InvalidEscapeSequence(offset) => LexingError::InvalidEscapeSequence(offset + by),
+      LexingError::InvalidNumber(offset) => LexingError::InvalidNumber(offset + by),
+    }
+  }
+}
+
+impl Default for LexingError {
+  fn default() -> Self {
+    LexingError::UnknownToken(0)
+  }
+}
+
+impl fmt::Display for LexingError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      LexingError::UnknownToken(offset) => write!(f, "unknown token at offset {offset}"),
+      LexingError::UnterminatedString(offset) => {
+        write!(f, "unterminated string at offset {offset}")
+      }
+      LexingError::InvalidEscapeSequence(offset) => {
+        write!(f, "invalid escape sequence at offset {offset}")
+      }
+      LexingError::InvalidNumber(offset) => write!(f, "invalid number at offset {offset}"),
+    }
+  }
+}
+
+impl std::error::Error for LexingError {}
+
+/// A token of a GraphQL document, as produced by [`tokenize`].
+///
+/// The payload of a token is its source text, e.g. `"a\n"` for a string, including the quotes and
+/// escape sequences. New kinds of tokens may be added, so matches need a wildcard arm.
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
+pub enum Token<'a> {
+  BraceOpen,
+  BraceClose,
+  ParenOpen,
+  ParenClose,
+  BracketOpen,
+  BracketClose,
+  Colon,
+  Equals,
+  Exclamation,
+  Question,
+  Ampersand,
+  Pipe,
+  Ellipsis,
+  /// The whole block string, including its delimiters.
+  BlockString(&'a str),
+  String(&'a str),
+  Int(&'a str),
+  Float(&'a str),
+  Bool(&'a str),
+  /// A directive name, including its `@`.
+  Directive(&'a str),
+  /// A variable name, including its `$`.
+  Variable(&'a str),
+  Identifier(&'a str),
+  /// A comment, including its `#`. Ignored like whitespace when printing.
+  Comment(&'a str),
+}
+
+/// The tokens as lexed by logos, converted to [`Token`] by [`Lexer`]. Kept private so that neither
+/// logos nor the strict flag in its extras are part of the public API.
+#[derive(Logos, Debug, PartialEq, Clone)]
+#[logos(skip r"[\s,]+")]
 #[logos(error = LexingError)]
-pub(crate) enum Token<'a> {
+// Whether to enforce the lexical rules of the spec, see `lex_number` and `lex_question`.
+#[logos(extras = bool)]
+enum LogosToken<'a> {
   #[token("{")]
   BraceOpen,
 
@@ -41,7 +147,7 @@ pub(crate) enum Token<'a> {
   #[token("!")]
   Exclamation,
 
-  #[token("?")]
+  #[token("?", lex_question)]
   Question,
 
   #[token("&")]
@@ -53,8 +159,8 @@ pub(crate) enum Token<'a> {
   #[token("...")]
   Ellipsis,
 
-  #[regex(r#"""""#)]
-  BlockStringDelimiter,
+  #[token(r#"""""#, lex_block_string)]
+  BlockString(&'a str),
 
   #[regex(r#""([^"\\]*(\\.[^"\\]*)*)""#, |lexer| match lexer.slice() {
       s if s.contains(['\n', '\r']) => Err(LexingError::UnterminatedString(lexer.span().start)),
@@ -62,10 +168,10 @@ pub(crate) enum Token<'a> {
   })]
   String(&'a str),
 
-  #[regex("-?[0-9]+")]
+  #[regex("-?[0-9]+", lex_number)]
   Int(&'a str),
 
-  #[regex("-?[0-9]+\\.[0-9]+(e-?[0-9]+)?")]
+  #[regex(r"-?[0-9]+(\.[0-9]+([eE][+-]?[0-9]+)?|[eE][+-]?[0-9]+)", lex_number)]
   Float(&'a str),
 
   #[regex("true|false")]
@@ -79,37 +185,271 @@ pub(crate) enum Token<'a> {
 
   #[regex(r"[a-zA-Z_][a-zA-Z0-9_]*")]
   Identifier(&'a str),
+
+  #[regex(r"#[^\r\n]*")]
+  Comment(&'a str),
+}
+
+impl<'a> From<LogosToken<'a>> for Token<'a> {
+  fn from(token: LogosToken<'a>) -> Self {
+    match token {
+      LogosToken::BraceOpen => Token::BraceOpen,
+      LogosToken::BraceClose => Token::BraceClose,
+      LogosToken::ParenOpen => Token::ParenOpen,
+      LogosToken::ParenClose => Token::ParenClose,
+      LogosToken::BracketOpen => Token::BracketOpen,
+      LogosToken::BracketClose => Token::BracketClose,
+      LogosToken::Colon => Token::Colon,
+      LogosToken::Equals => Token::Equals,
+      LogosToken::Exclamation => Token::Exclamation,
+      LogosToken::Question => Token::Question,
+      LogosToken::Ampersand => Token::Ampersand,
+      LogosToken::Pipe => Token::Pipe,
+      LogosToken::Ellipsis => Token::Ellipsis,
+      LogosToken::BlockString(value) => Token::BlockString(value),
+      LogosToken::String(value) => Token::String(value),
+      LogosToken::Int(value) => Token::Int(value),
+      LogosToken::Float(value) => Token::Float(value),
+      LogosToken::Bool(value) => Token::Bool(value),
+      LogosToken::Directive(value) => Token::Directive(value),
+      LogosToken::Variable(value) => Token::Variable(value),
+      LogosToken::Identifier(value) => Token::Identifier(value),
+      LogosToken::Comment(value) => Token::Comment(value),
+    }
+  }
+}
+
+/// Splits a document into [`Token`]s, like [`tokenize`], and keeps track of their spans.
+pub(crate) struct Lexer<'a> {
+  inner: logos::Lexer<'a, LogosToken<'a>>,
+}
+
+impl<'a> Lexer<'a> {
+  pub(crate) fn new(source: &'a str) -> Self {
+    Self::with_strictness(source, false)
+  }
+
+  /// With `strict` set, the lexical rules of the spec are enforced, see `lex_number` and
+  /// `lex_question`.
+  pub(crate) fn with_strictness(source: &'a str, strict: bool) -> Self {
+    Lexer {
+      inner: LogosToken::lexer_with_extras(source, strict),
+    }
+  }
+
+  /// Byte range of the last token in the source.
+  pub(crate) fn span(&self) -> Range<usize> {
+    self.inner.span()
+  }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+  type Item = Result<Token<'a>, LexingError>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.inner.next().map(|token| match token {
+      Ok(token) => Ok(token.into()),
+      // Reported at offset 0 by logos, see `LexingError::default`.
+      Err(LexingError::UnknownToken(_)) => Err(LexingError::UnknownToken(self.span().start)),
+      Err(e) => Err(e),
+    })
+  }
+}
+
+/// Splits a GraphQL document into its tokens, skipping whitespace and commas.
+///
+/// Tokens can be transformed and printed again with [`print_tokens`](crate::print_tokens).
+///
+/// # Examples
+///
+/// ```
+/// use graphql_minify::{tokenize, Token};
+///
+/// let tokens: Vec<_> = tokenize("{ a(b: $c) }").collect::<Result<_, _>>().unwrap();
+///
+/// assert_eq!(tokens[1], Token::Identifier("a"));
+/// assert_eq!(tokens[5], Token::Variable("$c"));
+/// ```
+///
+/// # Errors
+///
+/// Yields an error for every part of the document that could not be lexed and carries on after it.
+pub fn tokenize(source: &str) -> impl Iterator<Item = Result<Token<'_>, LexingError>> {
+  Lexer::new(source)
 }
 
 impl<'a> Token<'a> {
-  pub(crate) fn parse_block_string(&self, lexer: &mut Lexer<'a, Token<'a>>) -> String {
-    let mut lines = vec![];
-    let mut current_line = String::new();
-
-    let remainder = lexer.remainder();
-    let mut block_lexer = BlockStringToken::lexer(remainder);
-
-    while let Some(Ok(token)) = block_lexer.next() {
-      match token {
-        BlockStringToken::NewLine => {
-          lines.push(current_line);
-          current_line = String::new();
-        }
-        BlockStringToken::Text | BlockStringToken::Quote | BlockStringToken::EscapeSeq => {
-          current_line.push_str(block_lexer.slice())
-        }
-        BlockStringToken::EscapedTripleQuote => current_line.push_str(r#"""""#),
-        BlockStringToken::TripleQuote => break,
-      }
+  /// Returns the same kind of token without borrowing from the source, which is all that is needed
+  /// to decide on spacing once the source it was lexed from is gone.
+  pub(crate) fn detached(&self) -> Token<'static> {
+    match self {
+      Token::BraceOpen => Token::BraceOpen,
+      Token::BraceClose => Token::BraceClose,
+      Token::ParenOpen => Token::ParenOpen,
+      Token::ParenClose => Token::ParenClose,
+      Token::BracketOpen => Token::BracketOpen,
+      Token::BracketClose => Token::BracketClose,
+      Token::Colon => Token::Colon,
+      Token::Equals => Token::Equals,
+      Token::Exclamation => Token::Exclamation,
+      Token::Question => Token::Question,
+      Token::Ampersand => Token::Ampersand,
+      Token::Pipe => Token::Pipe,
+      Token::Ellipsis => Token::Ellipsis,
+      Token::BlockString(_) => Token::BlockString(""),
+      Token::String(_) => Token::String(""),
+      Token::Int(_) => Token::Int(""),
+      Token::Float(_) => Token::Float(""),
+      Token::Bool(_) => Token::Bool(""),
+      Token::Directive(_) => Token::Directive(""),
+      Token::Variable(_) => Token::Variable(""),
+      Token::Identifier(_) => Token::Identifier(""),
+      Token::Comment(_) => Token::Comment(""),
     }
+  }
 
-    if !current_line.is_empty() {
-      lines.push(current_line);
+  /// The source text of the token.
+  pub fn as_str(&self) -> &'a str {
+    match self {
+      Token::BraceOpen => "{",
+      Token::BraceClose => "}",
+      Token::ParenOpen => "(",
+      Token::ParenClose => ")",
+      Token::BracketOpen => "[",
+      Token::BracketClose => "]",
+      Token::Colon => ":",
+      Token::Equals => "=",
+      Token::Exclamation => "!",
+      Token::Question => "?",
+      Token::Ampersand => "&",
+      Token::Pipe => "|",
+      Token::Ellipsis => "...",
+      Token::BlockString(value)
+      | Token::String(value)
+      | Token::Int(value)
+      | Token::Float(value)
+      | Token::Bool(value)
+      | Token::Directive(value)
+      | Token::Variable(value)
+      | Token::Identifier(value)
+      | Token::Comment(value) => value,
     }
+  }
+}
+
+/// When lexing strictly, rejects numbers with leading zeros and numbers that are directly followed by
+/// a digit, `.` or name character, e.g. `0123`, `1.` or `123abc`, which are separate tokens otherwise.
+fn lex_number<'a>(lexer: &mut logos::Lexer<'a, LogosToken<'a>>) -> Result<&'a str, LexingError> {
+  let number = lexer.slice();
+  if !lexer.extras {
+    return Ok(number);
+  }
+
+  let integer_start = lexer.span().start + number.starts_with('-') as usize;
+  if let [b'0', b'0'..=b'9', ..] = lexer.source().as_bytes()[integer_start..] {
+    return Err(LexingError::InvalidNumber(integer_start + 1));
+  }
 
-    lexer.bump(remainder.len() - block_lexer.remainder().len());
+  match lexer.remainder().chars().next() {
+    Some(ch) if ch == '.' || ch == '_' || ch.is_ascii_alphanumeric() => {
+      Err(LexingError::InvalidNumber(lexer.span().end))
+    }
+    _ => Ok(number),
+  }
+}
+
+/// When lexing strictly, rejects `?`, which is not a punctuator of the spec. It is only lexed for the
+/// nullability operators of the client controlled nullability proposal.
+fn lex_question<'a>(lexer: &mut logos::Lexer<'a, LogosToken<'a>>) -> Result<(), LexingError> {
+  if lexer.extras {
+    return Err(LexingError::UnknownToken(lexer.span().start));
+  }
+
+  Ok(())
+}
+
+/// Consumes a block string up to and including its closing delimiter, or the rest of the source if
+/// it is not terminated.
+fn lex_block_string<'a>(lexer: &mut logos::Lexer<'a, LogosToken<'a>>) -> &'a str {
+  let remainder = lexer.remainder();
+  let mut block_lexer = BlockStringToken::lexer(remainder);
+
+  while let Some(Ok(token)) = block_lexer.next() {
+    if token == BlockStringToken::TripleQuote {
+      break;
+    }
+  }
+
+  lexer.bump(remainder.len() - block_lexer.remainder().len());
+  lexer.slice()
+}
+
+/// Whether `token` is followed by a name, in which case a following `query` is that name rather
+/// than the start of an operation.
+pub(crate) fn expects_name(token: &Token) -> bool {
+  matches!(
+    token,
+    Token::Identifier(
+      "query"
+        | "mutation"
+        | "subscription"
+        | "fragment"
+        | "on"
+        | "type"
+        | "interface"
+        | "union"
+        | "enum"
+        | "input"
+        | "scalar"
+        | "implements"
+    ) | Token::Ampersand
+      | Token::Pipe
+      | Token::Equals
+  )
+}
+
+/// Whether `keyword` starts a definition at the top level of a document, unless it is a name.
+pub(crate) fn is_definition_keyword(keyword: &str) -> bool {
+  matches!(
+    keyword,
+    "query"
+      | "mutation"
+      | "subscription"
+      | "fragment"
+      | "schema"
+      | "scalar"
+      | "type"
+      | "interface"
+      | "union"
+      | "enum"
+      | "input"
+      | "directive"
+  )
+}
+
+#[cfg(test)]
+mod test {
+  use super::LexingError;
+
+  #[test]
+  fn locates_errors_by_line_and_column() {
+    let source = "{\r\n  a\r  b\n  \"c\n}";
+
+    assert_eq!(LexingError::UnknownToken(0).location(source), (1, 1));
+    assert_eq!(LexingError::UnknownToken(5).location(source), (2, 3));
+    assert_eq!(LexingError::UnknownToken(9).location(source), (3, 3));
+    assert_eq!(LexingError::UnterminatedString(13).location(source), (4, 3));
+    assert_eq!(LexingError::UnknownToken(100).location(source), (5, 2));
+  }
+
+  #[test]
+  fn counts_columns_in_characters() {
+    assert_eq!(LexingError::UnknownToken(5).location("\"ä\" %"), (1, 5));
+  }
 
-    dedent_block_lines_mut(&mut lines);
-    print_block_string(lines.join("\n"))
+  #[test]
+  fn locates_offsets_inside_of_characters() {
+    assert_eq!(LexingError::UnknownToken(1).location("é"), (1, 1));
+    assert_eq!(LexingError::UnknownToken(4).location("a\n€"), (2, 1));
   }
 }
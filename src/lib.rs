@@ -1,5 +1,26 @@
+#[cfg(any(feature = "apollo-compiler", feature = "graphql-parser"))]
+mod ast;
 mod block_string;
+mod comments;
+mod document;
+mod format;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 mod lexer;
 mod minify;
-pub use minify::minify;
-pub use lexer::LexingError;
+mod normalize;
+mod operations;
+mod printer;
+mod stream;
+mod string;
+pub use comments::extract_comments;
+pub use format::format;
+pub use minify::{minify, minify_strict};
+pub use normalize::normalize;
+pub use operations::{list_operations, OperationInfo, OperationKind};
+pub use printer::print_tokens;
+pub use document::MinifiedDocument;
+pub use lexer::{tokenize, LexingError, Token};
+pub use stream::MinifyStream;
+#[cfg(any(feature = "apollo-compiler", feature = "graphql-parser"))]
+pub use ast::{minify_ast, MinifyAst};
@@ -1,5 +1,6 @@
-use crate::lexer::{LexingError, Token};
-use logos::Logos;
+use crate::block_string::minify_block_string;
+use crate::lexer::{Lexer, LexingError, Token};
+use std::ops::Range;
 
 /// Strips characters that are not significant to the validity or execution of a GraphQL document.
 /// It is functionally equivalent to [`stripIgnoredCharacters`](https://graphql-js.org/api/function/stripignoredcharacters/) defined in the [GraphQL spec](https://spec.graphql.org/June2018/#sec-Source-Text.Ignored-Tokens).
@@ -37,28 +38,113 @@ use logos::Logos;
 ///
 /// This function does not use any unsafe code.
 pub fn minify<T: AsRef<str>>(value: T) -> Result<String, LexingError> {
-  let mut lexer = Token::lexer(value.as_ref());
   let mut result = String::new();
-  let mut last_token = None;
+  minify_into(value.as_ref(), &mut result, None, false, false)?;
+
+  Ok(result)
+}
+
+/// Like [`minify`], but also checks the lookahead restrictions that the GraphQL spec puts on
+/// numbers and only accepts the punctuators of the spec, so that it can double as a check that a
+/// document is lexically valid.
+///
+/// [`minify`] splits `123abc` into the tokens `123` and `abc`, while the spec, like GraphQL.js,
+/// does not allow a number to be directly followed by a digit, `.` or name character. Numbers with
+/// leading zeros such as `0123` are rejected as well. [`minify`] also accepts the `?` of the
+/// client controlled nullability proposal, which is rejected as an unknown token here.
+///
+/// # Examples
+///
+/// ```
+/// use graphql_minify::{minify, minify_strict, LexingError};
+///
+/// assert_eq!(minify("{ a(b: 123abc) }").unwrap(), "{a(b:123 abc)}");
+/// assert_eq!(minify_strict("{ a(b: 123abc) }"), Err(LexingError::InvalidNumber(10)));
+/// assert_eq!(minify_strict("{ a? }"), Err(LexingError::UnknownToken(3)));
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if the lexing process encounters an unexpected character, a
+/// number that breaks the lookahead restrictions or a punctuator that is not part of the spec.
+pub fn minify_strict<T: AsRef<str>>(value: T) -> Result<String, LexingError> {
+  let mut result = String::new();
+  minify_into(value.as_ref(), &mut result, None, false, true)?;
+
+  Ok(result)
+}
+
+/// Appends the minified form of `source` to `result`, spacing the first token against `last_token`.
+///
+/// With `partial` set, `source` is treated as the beginning of a larger document: output is only
+/// kept up to the last ignored character, since any token after it could still be extended by the
+/// next chunk. Returns the number of bytes of `source` that were consumed and the last token written.
+/// With `strict` set, the lexical rules of the spec are enforced, see [`minify_strict`].
+pub(crate) fn minify_into<'a>(
+  source: &'a str,
+  result: &mut String,
+  mut last_token: Option<Token<'a>>,
+  partial: bool,
+  strict: bool,
+) -> Result<(usize, Option<Token<'a>>), LexingError> {
+  let mut lexer = Lexer::with_strictness(source, strict);
+  let mut committed = (result.len(), 0, last_token.clone());
+  let mut last_end = 0;
+  let mut complete = true;
 
   while let Some(token) = lexer.next() {
+    if partial && lexer.span().start > last_end {
+      committed = (result.len(), last_end, last_token.clone());
+    }
+
     let token = match token {
       Ok(token) => token,
+      Err(_) if partial && may_be_incomplete(source, lexer.span()) => {
+        complete = false;
+        break;
+      }
       Err(e) => return Err(e),
     };
 
+    if let Token::Comment(_) = token {
+      continue;
+    }
+
     if needs_space(&token, &last_token) {
       result.push(' ');
     }
 
     match token {
-      Token::BlockStringDelimiter => result.push_str(&token.parse_block_string(&mut lexer)),
-      _ => result.push_str(lexer.slice()),
+      Token::BlockString(raw) => result.push_str(&minify_block_string(raw)),
+      _ => result.push_str(token.as_str()),
     }
     last_token = Some(token);
+    last_end = lexer.span().end;
   }
 
-  Ok(result)
+  if !partial {
+    return Ok((source.len(), last_token));
+  }
+  if complete && last_end < source.len() {
+    return Ok((last_end, last_token));
+  }
+
+  let (len, consumed, last_token) = committed;
+  result.truncate(len);
+
+  Ok((consumed, last_token))
+}
+
+/// Whether a lexing error at `span` near the end of a partial document could still turn into a
+/// valid token once the next chunk arrives, like an unclosed string or a `..` that becomes `...`.
+fn may_be_incomplete(source: &str, span: Range<usize>) -> bool {
+  let rest = &source[span.start..];
+
+  if rest.starts_with('"') {
+    !rest.contains(['\n', '\r'])
+  } else {
+    span.end == source.len() || "...".starts_with(rest)
+  }
 }
 
 fn is_non_punctuator(token: &Token) -> bool {
@@ -82,26 +168,17 @@ fn is_non_punctuator(token: &Token) -> bool {
   )
 }
 
-fn needs_space_after_token(token: &Token) -> bool {
-  matches!(
-    token,
-    Token::Variable(_) | Token::String(_) | Token::Identifier(_) | Token::Directive(_)
-  )
-}
-
-fn needs_space_before_token(token: &Token) -> bool {
-  matches!(
-    token,
-    Token::Identifier(_) | Token::BlockStringDelimiter | Token::Ellipsis
-  )
+/// Variables and directives start with a punctuator but end with a name, so they are spaced like
+/// names against the token that follows them.
+fn ends_with_non_punctuator(token: &Token) -> bool {
+  is_non_punctuator(token) || matches!(token, Token::Variable(_) | Token::Directive(_))
 }
 
-fn needs_space(curr_token: &Token, last_token: &Option<Token>) -> bool {
+pub(crate) fn needs_space(curr_token: &Token, last_token: &Option<Token>) -> bool {
   match last_token {
-    Some(last) if is_non_punctuator(last) => {
+    Some(last) if ends_with_non_punctuator(last) => {
       is_non_punctuator(curr_token) || *curr_token == Token::Ellipsis
     }
-    Some(last) if needs_space_after_token(last) => needs_space_before_token(curr_token),
     _ => false,
   }
 }
@@ -109,7 +186,7 @@ fn needs_space(curr_token: &Token, last_token: &Option<Token>) -> bool {
 #[cfg(test)]
 mod test {
   use crate::lexer::LexingError;
-  use super::minify;
+  use super::{minify, minify_strict};
   use indoc::indoc;
 
   #[test]
@@ -161,6 +238,14 @@ mod test {
     ));
   }
 
+  #[test]
+  fn errs_with_offset_of_unknown_token() {
+    let query = "{ foo(arg: %) }";
+
+    assert_eq!(minify(query), Err(LexingError::UnknownToken(11)));
+    assert_eq!(minify(query).unwrap_err().location(query), (1, 12));
+  }
+
   #[test]
   fn strips_non_parsable_document() {
     let query = r#"{ foo(arg: "str""#;
@@ -190,6 +275,13 @@ mod test {
     assert_eq!(minify("1#comment\n, \n").unwrap(), "1");
   }
 
+  #[test]
+  fn replace_comments_between_non_punctuator_tokens_with_space() {
+    assert_eq!(minify("a#comment\nb").unwrap(), "a b");
+    assert_eq!(minify("a#comment\n#comment\r\n1").unwrap(), "a 1");
+    assert_eq!(minify("{#comment\n}").unwrap(), "{}");
+  }
+
   #[test]
   fn strips_ignored_tokens_between_punctuator_tokens() {
     assert_eq!(minify("[,)").unwrap(), "[)");
@@ -227,6 +319,63 @@ mod test {
     assert_eq!(minify("a \t 1").unwrap(), "a 1");
   }
 
+  #[test]
+  fn replace_ignored_tokens_after_variables_and_directives_with_space() {
+    assert_eq!(
+      minify("[$a 1, $b true, $c \"d\"]").unwrap(),
+      "[$a 1$b true$c \"d\"]"
+    );
+    assert_eq!(
+      minify("scalar A @d\n\"B\" type B").unwrap(),
+      "scalar A@d \"B\" type B"
+    );
+    assert_eq!(minify("[$a, $b]").unwrap(), "[$a$b]");
+  }
+
+  #[test]
+  fn keeps_exponents_of_floats() {
+    assert_eq!(minify("[1e5, 1E+5, 1.5e-5]").unwrap(), "[1e5 1E+5 1.5e-5]");
+  }
+
+  #[test]
+  fn splits_numbers_from_following_names_unless_strict() {
+    assert_eq!(
+      minify("[123abc, 0123, 1.5e]").unwrap(),
+      "[123 abc 0123 1.5 e]"
+    );
+    assert_eq!(
+      minify_strict("[123 abc, 0, -0.5e-0]").unwrap(),
+      "[123 abc 0 -0.5e-0]"
+    );
+  }
+
+  #[test]
+  fn errs_on_lookahead_violations_when_strict() {
+    assert_eq!(
+      minify_strict("[123abc]"),
+      Err(LexingError::InvalidNumber(4))
+    );
+    assert_eq!(minify_strict("[1_000]"), Err(LexingError::InvalidNumber(2)));
+    assert_eq!(minify_strict("[1.5e]"), Err(LexingError::InvalidNumber(4)));
+    assert_eq!(minify_strict("[1e5.0]"), Err(LexingError::InvalidNumber(4)));
+    assert_eq!(minify_strict("[1.]"), Err(LexingError::InvalidNumber(2)));
+    assert_eq!(minify_strict("[-0123]"), Err(LexingError::InvalidNumber(3)));
+    assert_eq!(minify_strict("[00.5]"), Err(LexingError::InvalidNumber(2)));
+    assert_eq!(minify(".5"), Err(LexingError::UnknownToken(0)));
+  }
+
+  #[test]
+  fn errs_on_punctuators_outside_of_the_spec_when_strict() {
+    assert_eq!(minify("{ a? b! }").unwrap(), "{a?b!}");
+    assert_eq!(minify_strict("{ a? }"), Err(LexingError::UnknownToken(3)));
+    assert_eq!(minify_strict("{ a [b] }").unwrap(), "{a[b]}");
+    assert_eq!(minify_strict("{ ..a }"), Err(LexingError::UnknownToken(2)));
+    assert_eq!(
+      minify_strict("{ ....a }"),
+      Err(LexingError::UnknownToken(5))
+    );
+  }
+
   #[test]
   fn does_not_strip_ignored_tokens_embedded_in_the_string() {
     assert_eq!(minify("\" \"").unwrap(), "\" \"");
@@ -0,0 +1,177 @@
+use crate::block_string::minify_block_string;
+use crate::lexer::{expects_name, Lexer, LexingError, Token};
+use crate::printer::Printer;
+use crate::string::{parse_string, print_string};
+
+/// Minifies a GraphQL document into the exact output of GraphQL.js'
+/// `stripIgnoredCharacters(print(parse(document)))`, so that hashes of normalized documents match
+/// the ones computed in JavaScript.
+///
+/// On top of what [`minify`](crate::minify) does, this
+/// - re-escapes strings the way `print` does, e.g. `"\u00E9\/"` becomes `"é/"`,
+/// - prints an anonymous `query` without variables or directives in its `{ ... }` shorthand form,
+/// - drops the optional leading `&` of implemented interfaces and `|` of union members and
+///   directive locations.
+///
+/// The output is only guaranteed to match for documents that GraphQL.js parses.
+///
+/// # Examples
+///
+/// ```
+/// use graphql_minify::normalize;
+///
+/// let original = r#"
+/// query {
+///   greet(name: "J\u00fcrgen") { text }
+/// }
+/// "#;
+/// let normalized = normalize(original).unwrap();
+///
+/// assert_eq!(normalized, r#"{greet(name:"Jürgen"){text}}"#);
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if the lexing process encounters an unexpected character or
+/// a string contains an invalid escape sequence.
+pub fn normalize<T: AsRef<str>>(value: T) -> Result<String, LexingError> {
+  let mut lexer = Lexer::new(value.as_ref());
+  let mut tokens = vec![];
+
+  while let Some(token) = lexer.next() {
+    let token = token?;
+
+    let text = match token {
+      Token::Comment(_) => continue,
+      Token::BlockString(raw) => Some(minify_block_string(raw)),
+      Token::String(raw) => {
+        Some(print_string(parse_string(raw).map_err(|offset| {
+          LexingError::InvalidEscapeSequence(lexer.span().start + offset)
+        })?))
+      }
+      _ => None,
+    };
+    tokens.push((token, text));
+  }
+
+  let mut printer = Printer::default();
+  let mut depth = 0usize;
+
+  for (i, (token, text)) in tokens.iter().enumerate() {
+    let before = |n: usize| i.checked_sub(n).map(|i| &tokens[i].0);
+
+    let omitted = match token {
+      Token::Identifier("query") => {
+        depth == 0
+          && matches!(tokens.get(i + 1), Some((Token::BraceOpen, _)))
+          && !before(1).is_some_and(expects_name)
+      }
+      Token::Ampersand => {
+        matches!(before(1), Some(Token::Identifier("implements")))
+          && !matches!(
+            before(2),
+            Some(Token::Identifier("implements") | Token::Ampersand)
+          )
+      }
+      Token::Pipe => matches!(before(1), Some(Token::Equals | Token::Identifier("on"))),
+      _ => false,
+    };
+
+    match token {
+      Token::BraceOpen => depth += 1,
+      Token::BraceClose => depth = depth.saturating_sub(1),
+      _ => {}
+    }
+
+    match text {
+      _ if omitted => {}
+      Some(text) => printer.push_str(token, text),
+      None => printer.push(token.clone()),
+    }
+  }
+
+  Ok(printer.finish())
+}
+
+#[cfg(test)]
+mod test {
+  use super::normalize;
+  use crate::lexer::LexingError;
+
+  #[test]
+  fn prints_anonymous_queries_in_shorthand_form() {
+    assert_eq!(normalize("query { a }").unwrap(), "{a}");
+    assert_eq!(normalize("query Q { a }").unwrap(), "query Q{a}");
+    assert_eq!(
+      normalize("query ($a: Int) { a }").unwrap(),
+      "query($a:Int){a}"
+    );
+    assert_eq!(normalize("query @d { a }").unwrap(), "query@d{a}");
+    assert_eq!(normalize("{ query }").unwrap(), "{query}");
+    assert_eq!(
+      normalize("type query { a: Int }").unwrap(),
+      "type query{a:Int}"
+    );
+    assert_eq!(
+      normalize("scalar Date query { a }").unwrap(),
+      "scalar Date{a}"
+    );
+  }
+
+  #[test]
+  fn drops_leading_separators() {
+    assert_eq!(
+      normalize("type A implements & B & C").unwrap(),
+      "type A implements B&C"
+    );
+    assert_eq!(normalize("union U = | A | B").unwrap(), "union U=A|B");
+    assert_eq!(
+      normalize("directive @d on | FIELD | QUERY").unwrap(),
+      "directive@d on FIELD|QUERY"
+    );
+  }
+
+  #[test]
+  fn reescapes_strings() {
+    assert_eq!(
+      normalize(r#"{ a(b: "A\/\u{1F600}") }"#).unwrap(),
+      r#"{a(b:"A/😀")}"#
+    );
+    assert_eq!(
+      normalize("{ a(b: \"\u{1}\") }").unwrap(),
+      r#"{a(b:"\u0001")}"#
+    );
+  }
+
+  #[test]
+  fn errs_with_offset_of_invalid_escape_sequence() {
+    assert_eq!(
+      normalize(r#"{ a(b: "c\d") }"#),
+      Err(LexingError::InvalidEscapeSequence(9))
+    );
+  }
+
+  #[test]
+  fn test_normalize_query() {
+    let query = include_str!("../data/normalize_query.gql");
+    let expected = include_str!("../data/normalize_query_expected.gql");
+
+    assert_eq!(normalize(query).unwrap(), expected);
+  }
+
+  #[test]
+  fn test_normalize_schema() {
+    let schema = include_str!("../data/normalize_schema.gql");
+    let expected = include_str!("../data/normalize_schema_expected.gql");
+
+    assert_eq!(normalize(schema).unwrap(), expected);
+  }
+
+  #[test]
+  fn test_normalize_kitchen_sink_schema() {
+    let schema = include_str!("../data/kitchen_sink_schema.gql");
+    let expected = include_str!("../data/kitchen_sink_schema_normalized.gql");
+
+    assert_eq!(normalize(schema).unwrap(), expected);
+  }
+}
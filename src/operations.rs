@@ -0,0 +1,241 @@
+use crate::lexer::{expects_name, is_definition_keyword, Lexer, LexingError, Token};
+use std::ops::Range;
+
+/// The type of an operation.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum OperationKind {
+  Query,
+  Mutation,
+  Subscription,
+}
+
+/// An operation defined in a document, as found by [`list_operations`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct OperationInfo<'a> {
+  /// `None` for anonymous operations.
+  pub name: Option<&'a str>,
+  pub kind: OperationKind,
+  /// Names of the declared variables, without the leading `$`.
+  pub variable_names: Vec<&'a str>,
+  /// Byte range of the whole operation in the document, from its keyword (or the opening brace
+  /// of a shorthand query) to its closing brace.
+  pub span: Range<usize>,
+}
+
+/// Lists the operations defined in a GraphQL document, in the order they appear, without parsing
+/// it into an AST.
+///
+/// # Examples
+///
+/// ```
+/// use graphql_minify::{list_operations, OperationKind};
+///
+/// let document = r#"
+/// query GetUser($id: ID!) { user(id: $id) { name } }
+/// fragment Name on User { name }
+/// mutation { logout }
+/// "#;
+/// let operations = list_operations(document).unwrap();
+///
+/// assert_eq!(operations.len(), 2);
+/// assert_eq!(operations[0].name, Some("GetUser"));
+/// assert_eq!(operations[0].variable_names, ["id"]);
+/// assert_eq!(operations[1].kind, OperationKind::Mutation);
+/// assert_eq!(&document[operations[1].span.clone()], "mutation { logout }");
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if the lexing process encounters an unexpected character.
+pub fn list_operations(source: &str) -> Result<Vec<OperationInfo<'_>>, LexingError> {
+  let mut lexer = Lexer::new(source);
+  let mut operations: Vec<OperationInfo> = vec![];
+  // Whether the last definition at the top level is an operation that has not been closed yet.
+  let mut in_operation = false;
+  let mut in_variable_definitions = false;
+  // Whether the next brace at the top level opens the body of the current definition.
+  let mut expects_body = false;
+  let mut depth = 0usize;
+  let mut last_token: Option<Token> = None;
+
+  while let Some(token) = lexer.next() {
+    let token = token?;
+    let span = lexer.span();
+
+    if let Token::Comment(_) = token {
+      continue;
+    }
+
+    let top_level = depth == 0 && !last_token.as_ref().is_some_and(expects_name);
+    let operation = operations.last_mut().filter(|_| in_operation);
+
+    match (&token, operation) {
+      (Token::Identifier(keyword), _) if top_level && is_definition_keyword(keyword) => {
+        let kind = match *keyword {
+          "query" => Some(OperationKind::Query),
+          "mutation" => Some(OperationKind::Mutation),
+          "subscription" => Some(OperationKind::Subscription),
+          _ => None,
+        };
+
+        in_operation = kind.is_some();
+        expects_body = !matches!(*keyword, "scalar" | "union" | "directive");
+
+        if let Some(kind) = kind {
+          operations.push(OperationInfo {
+            name: None,
+            kind,
+            variable_names: vec![],
+            span: span.clone(),
+          });
+        }
+      }
+      (Token::Identifier(name), Some(operation)) if depth == 0 => {
+        operation.name = Some(name);
+      }
+      (Token::ParenOpen, Some(_)) if depth == 0 && expects_body => {
+        in_variable_definitions = matches!(last_token, Some(Token::Identifier(_)));
+      }
+      (Token::Variable(name), Some(operation)) if depth == 1 && in_variable_definitions => {
+        operation.variable_names.push(&name[1..]);
+      }
+      (Token::BraceOpen, _) if depth == 0 && !expects_body => {
+        in_operation = true;
+        operations.push(OperationInfo {
+          name: None,
+          kind: OperationKind::Query,
+          variable_names: vec![],
+          span: span.clone(),
+        });
+      }
+      _ => {}
+    }
+
+    match token {
+      Token::BraceOpen | Token::ParenOpen | Token::BracketOpen => {
+        if depth == 0 && token == Token::BraceOpen {
+          expects_body = false;
+        }
+        depth += 1;
+      }
+      Token::BraceClose | Token::ParenClose | Token::BracketClose => {
+        depth = depth.saturating_sub(1);
+        if depth == 0 && token == Token::ParenClose {
+          in_variable_definitions = false;
+        }
+      }
+      _ => {}
+    }
+
+    if let Some(operation) = operations.last_mut().filter(|_| in_operation) {
+      operation.span.end = lexer.span().end;
+      if depth == 0 && token == Token::BraceClose {
+        in_operation = false;
+      }
+    }
+
+    last_token = Some(token);
+  }
+
+  Ok(operations)
+}
+
+#[cfg(test)]
+mod test {
+  use super::{list_operations, OperationInfo, OperationKind};
+  use crate::lexer::LexingError;
+
+  #[test]
+  fn lists_named_and_anonymous_operations() {
+    let document =
+      "query A { a } mutation B($x: Int = 1, $y: [In!] @d) { b } subscription { c } { d }";
+
+    assert_eq!(
+      list_operations(document).unwrap(),
+      [
+        OperationInfo {
+          name: Some("A"),
+          kind: OperationKind::Query,
+          variable_names: vec![],
+          span: 0..13,
+        },
+        OperationInfo {
+          name: Some("B"),
+          kind: OperationKind::Mutation,
+          variable_names: vec!["x", "y"],
+          span: 14..57,
+        },
+        OperationInfo {
+          name: None,
+          kind: OperationKind::Subscription,
+          variable_names: vec![],
+          span: 58..76,
+        },
+        OperationInfo {
+          name: None,
+          kind: OperationKind::Query,
+          variable_names: vec![],
+          span: 77..82,
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn ignores_variables_outside_of_definitions() {
+    let operations = list_operations("query Q @d(a: $a) { f(b: $b) }").unwrap();
+
+    assert!(operations[0].variable_names.is_empty());
+  }
+
+  #[test]
+  fn skips_type_system_definitions_and_fragments() {
+    let document = r#"
+      type query implements & query { query: query }
+      fragment query on query { query }
+      union U = query
+      scalar S
+      { a }
+      directive @d on QUERY
+      "query" query query { a(b: """}""") }
+    "#;
+    let operations = list_operations(document).unwrap();
+    let names: Vec<_> = operations.iter().map(|operation| operation.name).collect();
+
+    assert_eq!(names, [None, Some("query")]);
+    assert!(document[operations[1].span.clone()].ends_with(r#""""}""") }"#));
+  }
+
+  #[test]
+  fn lists_operations_of_the_kitchen_sink_query() {
+    let query = include_str!("../data/kitchen_sink_query.gql");
+    let operations: Vec<_> = list_operations(query)
+      .unwrap()
+      .into_iter()
+      .map(|operation| (operation.kind, operation.name, operation.variable_names))
+      .collect();
+
+    assert_eq!(
+      operations,
+      [
+        (OperationKind::Query, Some("queryName"), vec!["foo", "site"]),
+        (OperationKind::Mutation, Some("likeStory"), vec![]),
+        (
+          OperationKind::Subscription,
+          Some("StoryLikeSubscription"),
+          vec!["input"]
+        ),
+        (OperationKind::Query, None, vec![]),
+        (OperationKind::Query, None, vec![]),
+      ]
+    );
+  }
+
+  #[test]
+  fn errs_on_invalid_token() {
+    assert_eq!(
+      list_operations("{ a(b: %) }"),
+      Err(LexingError::UnknownToken(7))
+    );
+  }
+}
@@ -0,0 +1,129 @@
+use crate::block_string::minify_block_string;
+use crate::lexer::Token;
+use crate::minify::needs_space;
+use std::fmt;
+
+/// Prints a single token in its minified form. This is its source text, except for block strings,
+/// which are dedented the same way [`minify`](crate::minify) does.
+impl fmt::Display for Token<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Token::BlockString(raw) => f.write_str(&minify_block_string(raw)),
+      token => f.write_str(token.as_str()),
+    }
+  }
+}
+
+/// Prints a sequence of tokens with a single space wherever two neighbouring tokens would otherwise
+/// run together, the same way as [`minify`](crate::minify). Comments are skipped.
+///
+/// Together with [`tokenize`](crate::tokenize) this allows transforming a document token by token,
+/// e.g. to rename or redact parts of it, and still get valid minified output.
+///
+/// # Examples
+///
+/// ```
+/// use graphql_minify::{print_tokens, tokenize, Token};
+///
+/// let tokens = tokenize(r#"{ login(password: "hunter2") }"#).map(|token| match token.unwrap() {
+///   Token::String(_) => Token::Identifier("REDACTED"),
+///   token => token,
+/// });
+///
+/// assert_eq!(print_tokens(tokens), "{login(password:REDACTED)}");
+/// ```
+pub fn print_tokens<'a, I: IntoIterator<Item = Token<'a>>>(tokens: I) -> String {
+  let mut printer = Printer::default();
+
+  for token in tokens {
+    if !matches!(token, Token::Comment(_)) {
+      printer.push(token);
+    }
+  }
+
+  printer.finish()
+}
+
+/// Joins tokens with a single space wherever two neighbouring tokens would otherwise run together.
+#[derive(Debug, Default)]
+pub(crate) struct Printer {
+  result: String,
+  last_token: Option<Token<'static>>,
+}
+
+impl Printer {
+  pub(crate) fn push(&mut self, token: Token<'_>) {
+    match token {
+      Token::BlockString(raw) => self.push_str(&token, &minify_block_string(raw)),
+      _ => self.push_str(&token, token.as_str()),
+    }
+  }
+
+  /// Writes `text` in place of the source text of `token`, e.g. a re-escaped string.
+  pub(crate) fn push_str(&mut self, token: &Token<'_>, text: &str) {
+    if needs_space(token, &self.last_token) {
+      self.result.push(' ');
+    }
+
+    self.result.push_str(text);
+    self.last_token = Some(token.detached());
+  }
+
+  pub(crate) fn finish(self) -> String {
+    self.result
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::print_tokens;
+  use crate::lexer::{tokenize, Token};
+  use crate::minify;
+
+  fn tokens(source: &str) -> Vec<Token<'_>> {
+    tokenize(source).collect::<Result<_, _>>().unwrap()
+  }
+
+  #[test]
+  fn displays_tokens_in_minified_form() {
+    assert_eq!(Token::Ellipsis.to_string(), "...");
+    assert_eq!(Token::Variable("$a").to_string(), "$a");
+    assert_eq!(Token::String(r#""é""#).to_string(), r#""é""#);
+    assert_eq!(
+      Token::BlockString("\"\"\"\n  a\n  b\n\"\"\"").to_string(),
+      "\"\"\"a\nb\"\"\""
+    );
+    assert_eq!(Token::BlockString("").to_string(), "\"\"\"\"\"\"");
+  }
+
+  #[test]
+  fn reproduces_minified_output() {
+    let query = include_str!("../data/kitchen_sink_query.gql");
+    let schema = include_str!("../data/kitchen_sink_schema.gql");
+
+    assert_eq!(print_tokens(tokens(query)), minify(query).unwrap());
+    assert_eq!(print_tokens(tokens(schema)), minify(schema).unwrap());
+  }
+
+  #[test]
+  fn spaces_transformed_tokens() {
+    let renamed = tokens("{ a ...b @c(d: 1) }")
+      .into_iter()
+      .map(|token| match token {
+        Token::Identifier("a") => Token::Variable("$a"),
+        Token::Directive(_) => Token::Ellipsis,
+        token => token,
+      });
+
+    assert_eq!(print_tokens(renamed), "{$a ...b ...(d:1)}");
+  }
+
+  #[test]
+  fn skips_comments() {
+    assert_eq!(print_tokens(tokens("a # b\n c")), "a c");
+    assert_eq!(
+      print_tokens([Token::Identifier("a"), Token::Comment("#"), Token::Int("1")]),
+      "a 1"
+    );
+  }
+}
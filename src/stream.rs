@@ -0,0 +1,170 @@
+use crate::lexer::{LexingError, Token};
+use crate::minify::minify_into;
+
+/// Minifies a GraphQL document that arrives in chunks, e.g. from a file or network stream.
+///
+/// Only the part of the document that cannot be affected by later chunks is minified on each
+/// [`write`](MinifyStream::write), so the full document never has to be held in memory at once.
+///
+/// # Examples
+///
+/// ```
+/// use graphql_minify::MinifyStream;
+///
+/// let mut stream = MinifyStream::new();
+/// let mut minified = String::new();
+///
+/// minified += &stream.write("query SomeQuery { some").unwrap();
+/// minified += &stream.write("Field { a b }\n").unwrap();
+/// minified += &stream.write("}").unwrap();
+/// minified += &stream.finish().unwrap();
+///
+/// assert_eq!(minified, "query SomeQuery{someField{a b}}");
+/// ```
+#[derive(Debug, Default)]
+pub struct MinifyStream {
+  buffer: String,
+  offset: usize,
+  last_token: Option<Token<'static>>,
+}
+
+impl MinifyStream {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Feeds the next chunk of the document and returns the minified output that became available.
+  ///
+  /// # Errors
+  ///
+  /// This function will return an error as soon as the lexing process encounters a character that
+  /// no later chunk can turn into a valid token. Tokens that may still be completed, like an
+  /// unclosed string at the end of the chunk, are only reported by [`finish`](MinifyStream::finish).
+  pub fn write<T: AsRef<str>>(&mut self, chunk: T) -> Result<String, LexingError> {
+    self.buffer.push_str(chunk.as_ref());
+    self.minify_buffer(true)
+  }
+
+  /// Minifies whatever is left of the document after the last chunk.
+  ///
+  /// # Errors
+  ///
+  /// This function will return an error if the lexing process encounters an unexpected character.
+  pub fn finish(mut self) -> Result<String, LexingError> {
+    self.minify_buffer(false)
+  }
+
+  fn minify_buffer(&mut self, partial: bool) -> Result<String, LexingError> {
+    let mut result = String::new();
+    let (consumed, last_token) = minify_into(
+      &self.buffer,
+      &mut result,
+      self.last_token.take(),
+      partial,
+      false,
+    )
+    .map_err(|e| e.shifted(self.offset))?;
+
+    self.last_token = last_token.map(|token| token.detached());
+    self.buffer.drain(..consumed);
+    self.offset += consumed;
+
+    Ok(result)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::MinifyStream;
+  use crate::lexer::LexingError;
+  use crate::minify;
+
+  fn minify_chunked(value: &str, chunk_size: usize) -> Result<String, LexingError> {
+    let mut stream = MinifyStream::new();
+    let mut result = String::new();
+
+    for chunk in value.as_bytes().chunks(chunk_size) {
+      result.push_str(&stream.write(std::str::from_utf8(chunk).unwrap())?);
+    }
+    result.push_str(&stream.finish()?);
+
+    Ok(result)
+  }
+
+  #[test]
+  fn matches_minify_for_any_chunk_size() {
+    let query = include_str!("../data/kitchen_sink_query.gql");
+    let schema = include_str!("../data/kitchen_sink_schema.gql");
+
+    for chunk_size in 1..64 {
+      assert_eq!(
+        minify_chunked(query, chunk_size).unwrap(),
+        minify(query).unwrap()
+      );
+      assert_eq!(
+        minify_chunked(schema, chunk_size).unwrap(),
+        minify(schema).unwrap()
+      );
+    }
+  }
+
+  #[test]
+  fn does_not_split_tokens_across_chunks() {
+    let mut stream = MinifyStream::new();
+
+    assert_eq!(stream.write("a 1.5e").unwrap(), "a");
+    assert_eq!(stream.write("5 \"b c").unwrap(), " 1.5e5");
+    assert_eq!(stream.write("\" \"\"").unwrap(), " \"b c\"");
+    assert_eq!(stream.write("\"d\n  e\"\"\"").unwrap(), "");
+    assert_eq!(stream.finish().unwrap(), " \"\"\"d\ne\"\"\"");
+  }
+
+  #[test]
+  fn writes_only_whole_tokens_before_ignored_characters() {
+    let mut stream = MinifyStream::new();
+
+    assert_eq!(stream.write("{ foo").unwrap(), "{");
+    assert_eq!(stream.write("bar # comm").unwrap(), "foobar");
+    assert_eq!(stream.write("ent baz\n").unwrap(), "");
+    assert_eq!(stream.write("qux ").unwrap(), " qux");
+    assert_eq!(stream.finish().unwrap(), "");
+  }
+
+  #[test]
+  fn reports_errors_with_offsets_into_the_whole_document() {
+    let mut stream = MinifyStream::new();
+
+    assert_eq!(stream.write("{ foo(arg: ").unwrap(), "{foo(arg:");
+    assert_eq!(stream.write("\"a").unwrap(), "");
+    assert_eq!(stream.finish(), Err(LexingError::UnknownToken(11)));
+  }
+
+  #[test]
+  fn returns_errors_that_later_chunks_can_not_fix_right_away() {
+    let mut stream = MinifyStream::new();
+    assert_eq!(stream.write("{ foo").unwrap(), "{");
+    assert_eq!(
+      stream.write(" % bar").unwrap_err(),
+      LexingError::UnknownToken(6)
+    );
+
+    let mut stream = MinifyStream::new();
+    assert_eq!(stream.write("{ foo(arg: ").unwrap(), "{foo(arg:");
+    assert_eq!(
+      stream.write("\"a\n\" bar").unwrap_err(),
+      LexingError::UnterminatedString(11)
+    );
+  }
+
+  #[test]
+  fn defers_errors_that_later_chunks_may_fix() {
+    let mut stream = MinifyStream::new();
+
+    assert_eq!(stream.write("{ a(b: \"c\\").unwrap(), "{a(b:");
+    assert_eq!(stream.write("\"d\", e: -").unwrap(), "\"c\\\"d\" e:");
+    assert_eq!(stream.write("1) .").unwrap(), "-1)");
+    assert_eq!(stream.write(".").unwrap(), "");
+    assert_eq!(stream.write(".f }").unwrap(), "...f");
+    assert_eq!(stream.finish().unwrap(), "}");
+  }
+}
@@ -0,0 +1,153 @@
+use std::fmt::Write;
+
+/// Prints `value` as a quoted string, escaping it the same way as `printString` from GraphQL.js.
+pub(crate) fn print_string<T: AsRef<str>>(value: T) -> String {
+  let value = value.as_ref();
+  let mut result = String::with_capacity(value.len() + 2);
+
+  result.push('"');
+  for ch in value.chars() {
+    match ch {
+      '"' => result.push_str(r#"\""#),
+      '\\' => result.push_str(r"\\"),
+      '\u{8}' => result.push_str(r"\b"),
+      '\u{c}' => result.push_str(r"\f"),
+      '\n' => result.push_str(r"\n"),
+      '\r' => result.push_str(r"\r"),
+      '\t' => result.push_str(r"\t"),
+      '\u{0}'..='\u{1f}' | '\u{7f}'..='\u{9f}' => {
+        write!(result, r"\u{:04X}", ch as u32).unwrap();
+      }
+      _ => result.push(ch),
+    }
+  }
+  result.push('"');
+
+  result
+}
+
+/// Decodes the escape sequences of a quoted string token, including its quotes, the same way as the
+/// GraphQL.js lexer. Fails with the byte offset of the backslash of an invalid escape sequence.
+pub(crate) fn parse_string(raw: &str) -> Result<String, usize> {
+  let body = &raw[1..raw.len() - 1];
+  let mut result = String::with_capacity(body.len());
+  let mut rest = body;
+
+  while let Some(start) = rest.find('\\') {
+    result.push_str(&rest[..start]);
+    let offset = raw.len() - 1 - rest.len() + start;
+    let escape = &rest[start..];
+
+    let (ch, len) = match escape.as_bytes().get(1) {
+      Some(b'"') => ('"', 2),
+      Some(b'\\') => ('\\', 2),
+      Some(b'/') => ('/', 2),
+      Some(b'b') => ('\u{8}', 2),
+      Some(b'f') => ('\u{c}', 2),
+      Some(b'n') => ('\n', 2),
+      Some(b'r') => ('\r', 2),
+      Some(b't') => ('\t', 2),
+      Some(b'u') => parse_unicode_escape(escape).ok_or(offset)?,
+      _ => return Err(offset),
+    };
+
+    result.push(ch);
+    rest = &escape[len..];
+  }
+  result.push_str(rest);
+
+  Ok(result)
+}
+
+/// Parses `\u{1F600}`, `\u00E9` or a surrogate pair like `\uD83D\uDE00` at the start of `escape`.
+fn parse_unicode_escape(escape: &str) -> Option<(char, usize)> {
+  if let Some(braced) = escape.strip_prefix("\\u{") {
+    let end = braced.find('}')?;
+    let digits = &braced[..end];
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+      return None;
+    }
+
+    let ch = u32::from_str_radix(digits, 16)
+      .ok()
+      .and_then(char::from_u32)?;
+    return Some((ch, end + 4));
+  }
+
+  let code = hex_code(escape.get(2..6)?)?;
+  if let Some(ch) = char::from_u32(code) {
+    return Some((ch, 6));
+  }
+
+  let trailing = escape.get(6..12)?.strip_prefix("\\u").and_then(hex_code)?;
+  if !(0xd800..0xdc00).contains(&code) || !(0xdc00..0xe000).contains(&trailing) {
+    return None;
+  }
+
+  let ch = char::from_u32(0x10000 + ((code - 0xd800) << 10) + (trailing - 0xdc00))?;
+  Some((ch, 12))
+}
+
+fn hex_code(digits: &str) -> Option<u32> {
+  if digits.len() != 4 || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+    return None;
+  }
+
+  u32::from_str_radix(digits, 16).ok()
+}
+
+#[cfg(test)]
+mod test {
+  use super::{parse_string, print_string};
+
+  #[test]
+  fn does_not_escape_printable_characters() {
+    assert_eq!(
+      print_string("Hello, World! / ü 😀"),
+      r#""Hello, World! / ü 😀""#
+    );
+  }
+
+  #[test]
+  fn escapes_quotes_and_backslashes() {
+    assert_eq!(print_string(r#"say "\o/""#), r#""say \"\\o/\"""#);
+  }
+
+  #[test]
+  fn escapes_control_characters() {
+    assert_eq!(print_string("\u{8}\u{c}\n\r\t"), r#""\b\f\n\r\t""#);
+    assert_eq!(print_string("\u{0}\u{1f}"), r#""\u0000\u001F""#);
+    assert_eq!(
+      print_string("\u{7f}\u{9f}\u{a0}"),
+      "\"\\u007F\\u009F\u{a0}\""
+    );
+  }
+
+  #[test]
+  fn decodes_simple_escape_sequences() {
+    assert_eq!(
+      parse_string(r#""\"\\\/\b\f\n\r\t""#),
+      Ok("\"\\/\u{8}\u{c}\n\r\t".to_owned())
+    );
+  }
+
+  #[test]
+  fn decodes_unicode_escape_sequences() {
+    assert_eq!(parse_string(r#""\u00e9\u00C9""#), Ok("éÉ".to_owned()));
+    assert_eq!(
+      parse_string(r#""\u{1F600}\u{0}""#),
+      Ok("😀\u{0}".to_owned())
+    );
+    assert_eq!(parse_string(r#""\uD83D\uDE00""#), Ok("😀".to_owned()));
+  }
+
+  #[test]
+  fn rejects_invalid_escape_sequences() {
+    assert_eq!(parse_string(r#""a\q""#), Err(2));
+    assert_eq!(parse_string(r#""\u12""#), Err(1));
+    assert_eq!(parse_string(r#""\u{}""#), Err(1));
+    assert_eq!(parse_string(r#""\u{110000}""#), Err(1));
+    assert_eq!(parse_string(r#""ä\uD83D""#), Err(3));
+    assert_eq!(parse_string(r#""\uDE00\uD83D""#), Err(1));
+  }
+}
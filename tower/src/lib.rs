@@ -0,0 +1,434 @@
+//! A [tower](https://docs.rs/tower) layer that minifies GraphQL requests on their way upstream.
+//!
+//! ```no_run
+//! # use tower::ServiceBuilder;
+//! # let upstream = tower::service_fn(|_: http::Request<http_body_util::Full<bytes::Bytes>>| async {
+//! #   Ok::<_, std::convert::Infallible>(http::Response::new(()))
+//! # });
+//! use graphql_minify_tower::MinifyLayer;
+//!
+//! let service = ServiceBuilder::new()
+//!   .layer(MinifyLayer::new().max_body_size(256 * 1024))
+//!   .service(upstream);
+//! ```
+
+use bytes::Bytes;
+use graphql_minify::minify;
+use http::header::{CONTENT_LENGTH, CONTENT_TYPE};
+use http::{HeaderValue, Method, Request};
+use http_body::Body;
+use http_body_util::{BodyExt, Limited};
+use serde::Deserialize;
+use serde_json::value::RawValue;
+use std::future::Future;
+use std::ops::Range;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Attached as a request extension to every request whose GraphQL documents were minified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MinifyStats {
+  /// Number of documents in the request, more than one for batched requests.
+  pub documents: usize,
+  /// Combined length of the documents before minification.
+  pub original_len: usize,
+  /// Combined length of the documents after minification.
+  pub minified_len: usize,
+}
+
+/// Largest body that is buffered to be minified unless configured otherwise, 1 MiB.
+pub const DEFAULT_MAX_BODY_SIZE: usize = 1024 * 1024;
+
+/// Applies [`MinifyService`] to wrapped services.
+#[derive(Debug, Clone)]
+pub struct MinifyLayer {
+  max_body_size: usize,
+}
+
+impl MinifyLayer {
+  pub fn new() -> Self {
+    MinifyLayer {
+      max_body_size: DEFAULT_MAX_BODY_SIZE,
+    }
+  }
+
+  /// Sets the largest body, in bytes, that is buffered to be minified. Bodies that may be larger,
+  /// including bodies of unknown length, are forwarded unchanged.
+  pub fn max_body_size(mut self, max_body_size: usize) -> Self {
+    self.max_body_size = max_body_size;
+    self
+  }
+}
+
+impl Default for MinifyLayer {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<S> Layer<S> for MinifyLayer {
+  type Service = MinifyService<S>;
+
+  fn layer(&self, inner: S) -> Self::Service {
+    MinifyService {
+      inner,
+      max_body_size: self.max_body_size,
+    }
+  }
+}
+
+/// Minifies the `query` of GraphQL POST requests before passing them to the inner service.
+///
+/// Bodies sent as `application/json`, either a single request or a batch, and as
+/// `application/graphql` are minified. Only the `query` members of JSON bodies are rewritten, the
+/// rest of the body is forwarded byte for byte, and requests without a `query`, like persisted
+/// query lookups, are skipped. Other requests, bodies larger than
+/// [`max_body_size`](MinifyLayer::max_body_size) and documents that fail to minify are forwarded
+/// unchanged so the upstream server can report the error.
+#[derive(Debug, Clone)]
+pub struct MinifyService<S> {
+  inner: S,
+  max_body_size: usize,
+}
+
+impl<S, B> Service<Request<B>> for MinifyService<S>
+where
+  S: Service<Request<B>> + Clone + Send + 'static,
+  S::Future: Send,
+  S::Error: Into<BoxError>,
+  B: Body + From<Bytes> + Send + 'static,
+  B::Data: Send,
+  B::Error: Into<BoxError>,
+{
+  type Response = S::Response;
+  type Error = BoxError;
+  type Future = Pin<Box<dyn Future<Output = Result<S::Response, BoxError>> + Send>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self.inner.poll_ready(cx).map_err(Into::into)
+  }
+
+  fn call(&mut self, request: Request<B>) -> Self::Future {
+    // The clone might not be ready, keep the service that was polled ready and use it.
+    let clone = self.inner.clone();
+    let mut inner = std::mem::replace(&mut self.inner, clone);
+
+    let max_body_size = self.max_body_size;
+    let fits = |size: u64| usize::try_from(size).is_ok_and(|size| size <= max_body_size);
+    let kind =
+      content_kind(&request).filter(|_| request.body().size_hint().upper().is_some_and(fits));
+
+    let Some(kind) = kind else {
+      let future = inner.call(request);
+      return Box::pin(async move { future.await.map_err(Into::into) });
+    };
+
+    Box::pin(async move {
+      let (mut parts, body) = request.into_parts();
+      let body = Limited::new(body, max_body_size)
+        .collect()
+        .await?
+        .to_bytes();
+
+      let body = match minify_body(kind, &body) {
+        Some((minified, stats)) => {
+          parts.extensions.insert(stats);
+          if parts.headers.contains_key(CONTENT_LENGTH) {
+            parts
+              .headers
+              .insert(CONTENT_LENGTH, HeaderValue::from(minified.len()));
+          }
+          minified
+        }
+        None => body,
+      };
+
+      inner
+        .call(Request::from_parts(parts, B::from(body)))
+        .await
+        .map_err(Into::into)
+    })
+  }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ContentKind {
+  Json,
+  GraphQL,
+}
+
+fn content_kind<B>(request: &Request<B>) -> Option<ContentKind> {
+  if request.method() != Method::POST {
+    return None;
+  }
+
+  let content_type = request.headers().get(CONTENT_TYPE)?.to_str().ok()?;
+  // Media types are case-insensitive.
+  let media_type = content_type.split(';').next()?.trim();
+  if media_type.eq_ignore_ascii_case("application/json") {
+    Some(ContentKind::Json)
+  } else if media_type.eq_ignore_ascii_case("application/graphql") {
+    Some(ContentKind::GraphQL)
+  } else {
+    None
+  }
+}
+
+fn minify_body(kind: ContentKind, body: &[u8]) -> Option<(Bytes, MinifyStats)> {
+  let mut stats = MinifyStats::default();
+
+  match kind {
+    ContentKind::GraphQL => {
+      let query = std::str::from_utf8(body).ok()?;
+      let minified = minify_query(query, &mut stats)?;
+
+      Some((Bytes::from(minified), stats))
+    }
+    ContentKind::Json => {
+      let json = std::str::from_utf8(body).ok()?;
+      let requests = if json.trim_start().starts_with('[') {
+        serde_json::from_str::<Vec<JsonRequest>>(json).ok()?
+      } else {
+        vec![serde_json::from_str::<JsonRequest>(json).ok()?]
+      };
+
+      let mut minified = String::with_capacity(json.len());
+      let mut last_end = 0;
+      for query in requests.into_iter().filter_map(|request| request.query) {
+        let Some((range, query)) = minify_json_query(json, query, &mut stats) else {
+          continue;
+        };
+        minified.push_str(&json[last_end..range.start]);
+        minified.push_str(&query);
+        last_end = range.end;
+      }
+      minified.push_str(&json[last_end..]);
+
+      (stats.documents > 0).then(|| (Bytes::from(minified), stats))
+    }
+  }
+}
+
+/// The part of a GraphQL request in a JSON body that gets minified, borrowed from the body so the
+/// rest of it can be forwarded unchanged.
+#[derive(Deserialize)]
+struct JsonRequest<'a> {
+  #[serde(borrow)]
+  query: Option<&'a RawValue>,
+}
+
+/// Returns the byte range of `query` in `json`, and the minified query to put there. Queries that
+/// are not strings, like `null`, are skipped.
+fn minify_json_query(
+  json: &str,
+  query: &RawValue,
+  stats: &mut MinifyStats,
+) -> Option<(Range<usize>, String)> {
+  let raw = query.get();
+  let start = raw.as_ptr() as usize - json.as_ptr() as usize;
+  let query = serde_json::from_str::<String>(raw).ok()?;
+  let minified = serde_json::to_string(&minify_query(&query, stats)?).ok()?;
+
+  Some((start..start + raw.len(), minified))
+}
+
+fn minify_query(query: &str, stats: &mut MinifyStats) -> Option<String> {
+  let minified = minify(query).ok()?;
+
+  stats.documents += 1;
+  stats.original_len += query.len();
+  stats.minified_len += minified.len();
+
+  Some(minified)
+}
+
+#[cfg(test)]
+mod test {
+  use super::{MinifyLayer, MinifyStats};
+  use bytes::Bytes;
+  use http::header::CONTENT_LENGTH;
+  use http::{HeaderValue, Method, Request};
+  use http_body_util::{BodyExt, Full};
+  use std::convert::Infallible;
+  use tower::{service_fn, Layer, ServiceExt};
+
+  async fn forward(request: Request<Full<Bytes>>) -> (Option<MinifyStats>, String) {
+    forward_with(MinifyLayer::new(), request).await
+  }
+
+  async fn forward_with(
+    layer: MinifyLayer,
+    request: Request<Full<Bytes>>,
+  ) -> (Option<MinifyStats>, String) {
+    let upstream = service_fn(|request: Request<Full<Bytes>>| async move {
+      let stats = request.extensions().get::<MinifyStats>().copied();
+      let body = request.into_body().collect().await.unwrap().to_bytes();
+
+      Ok::<_, Infallible>((stats, String::from_utf8(body.to_vec()).unwrap()))
+    });
+
+    layer.layer(upstream).oneshot(request).await.unwrap()
+  }
+
+  fn post(content_type: &str, body: &str) -> Request<Full<Bytes>> {
+    Request::builder()
+      .method(Method::POST)
+      .header("content-type", content_type)
+      .body(Full::from(body.to_string()))
+      .unwrap()
+  }
+
+  #[tokio::test]
+  async fn minifies_json_requests() {
+    let request = post(
+      "application/json; charset=utf-8",
+      r#"{"query": "query Q($id: ID!) {\n  user(id: $id) { name }\n}", "variables": {"id": 1}}"#,
+    );
+
+    let (stats, body) = forward(request).await;
+
+    assert_eq!(
+      body,
+      r#"{"query": "query Q($id:ID!){user(id:$id){name}}", "variables": {"id": 1}}"#
+    );
+    assert_eq!(
+      stats,
+      Some(MinifyStats {
+        documents: 1,
+        original_len: 46,
+        minified_len: 36,
+      })
+    );
+  }
+
+  #[tokio::test]
+  async fn minifies_batched_json_requests() {
+    let request = post(
+      "application/json",
+      r#"[{"query": "{ a }"}, {"query": "{ b { c } }"}]"#,
+    );
+
+    let (stats, body) = forward(request).await;
+
+    assert_eq!(body, r#"[{"query": "{a}"}, {"query": "{b{c}}"}]"#);
+    assert_eq!(stats.map(|stats| stats.documents), Some(2));
+  }
+
+  #[tokio::test]
+  async fn rewrites_only_the_query_of_json_requests() {
+    let request = post(
+      "application/json",
+      r#"{"variables":{"big":123456789012345678901234,"float":1e2,"z":1,"a":2},"query":"{ a(b: \"\u00e9\") }"}"#,
+    );
+
+    let (_, body) = forward(request).await;
+
+    assert_eq!(
+      body,
+      r#"{"variables":{"big":123456789012345678901234,"float":1e2,"z":1,"a":2},"query":"{a(b:\"é\")}"}"#
+    );
+  }
+
+  #[tokio::test]
+  async fn skips_batched_requests_without_query() {
+    let request = post(
+      "application/json",
+      r#"[{"extensions": {"persistedQuery": {"version": 1}}}, {"query": null}, {"query": "{ b }"}]"#,
+    );
+
+    let (stats, body) = forward(request).await;
+
+    assert_eq!(
+      body,
+      r#"[{"extensions": {"persistedQuery": {"version": 1}}}, {"query": null}, {"query": "{b}"}]"#
+    );
+    assert_eq!(stats.map(|stats| stats.documents), Some(1));
+  }
+
+  #[tokio::test]
+  async fn updates_the_content_length() {
+    let upstream = service_fn(|request: Request<Full<Bytes>>| async move {
+      let content_length = request.headers()[CONTENT_LENGTH].clone();
+      let body = request.into_body().collect().await.unwrap().to_bytes();
+
+      Ok::<_, Infallible>((content_length, body.len()))
+    });
+    let mut request = post("application/graphql", "{ a, b }");
+    request
+      .headers_mut()
+      .insert(CONTENT_LENGTH, HeaderValue::from(8));
+
+    let (content_length, len) = MinifyLayer::new()
+      .layer(upstream)
+      .oneshot(request)
+      .await
+      .unwrap();
+
+    assert_eq!(content_length, "5");
+    assert_eq!(len, 5);
+  }
+
+  #[tokio::test]
+  async fn forwards_large_bodies_unchanged() {
+    let layer = MinifyLayer::new().max_body_size(8);
+
+    assert_eq!(
+      forward_with(layer.clone(), post("application/graphql", "{ a, b }")).await,
+      (
+        Some(MinifyStats {
+          documents: 1,
+          original_len: 8,
+          minified_len: 5,
+        }),
+        "{a b}".to_string()
+      )
+    );
+    assert_eq!(
+      forward_with(layer, post("application/graphql", "{ a, b, c }")).await,
+      (None, "{ a, b, c }".to_string())
+    );
+  }
+
+  #[tokio::test]
+  async fn minifies_graphql_requests() {
+    let (stats, body) = forward(post("application/graphql", "{ a, b }")).await;
+
+    assert_eq!(body, "{a b}");
+    assert!(stats.is_some());
+  }
+
+  #[tokio::test]
+  async fn matches_media_types_case_insensitively() {
+    let (stats, body) = forward(post(
+      "Application/JSON; charset=utf-8",
+      r#"{"query": "{ a }"}"#,
+    ))
+    .await;
+
+    assert_eq!(body, r#"{"query": "{a}"}"#);
+    assert!(stats.is_some());
+  }
+
+  #[tokio::test]
+  async fn forwards_other_requests_unchanged() {
+    let invalid = r#"{"query": "{ a(arg: %) }"}"#;
+    assert_eq!(
+      forward(post("application/json", invalid)).await,
+      (None, invalid.to_string())
+    );
+    assert_eq!(
+      forward(post("text/plain", "{ a }")).await,
+      (None, "{ a }".to_string())
+    );
+
+    let get = Request::builder()
+      .uri("/graphql?query={a}")
+      .body(Full::from("{ a }"))
+      .unwrap();
+    assert_eq!(forward(get).await, (None, "{ a }".to_string()));
+  }
+}
@@ -1,4 +1,6 @@
 use cfg_if::cfg_if;
+use graphql_minify::LexingError;
+use std::str::Utf8Error;
 use wasm_bindgen::prelude::*;
 
 mod utils;
@@ -10,7 +12,261 @@ cfg_if! {
     }
 }
 
+#[wasm_bindgen(typescript_custom_section)]
+const MINIFY_OPTIONS: &'static str = r#"
+export interface MinifyOptions {
+  /**
+   * `"minify"` (the default) strips ignored characters, `"strict"` also rejects numbers that are
+   * directly followed by a name or a dot and punctuators outside of the spec such as `?`, and
+   * `"normalize"` returns the exact output of GraphQL.js'
+   * `stripIgnoredCharacters(print(parse(query)))`.
+   */
+  mode?: "minify" | "strict" | "normalize";
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+  #[wasm_bindgen(typescript_type = "MinifyOptions")]
+  pub type MinifyOptions;
+
+  #[wasm_bindgen(method, getter)]
+  fn mode(this: &MinifyOptions) -> Option<String>;
+}
+
+#[wasm_bindgen]
+pub fn minify(query: &str, options: Option<MinifyOptions>) -> Result<String, GraphQLMinifyError> {
+  minifier(options)?(query)
+}
+
+/// Minifies every document in `queries`, failing on the first one that can not be minified.
+#[wasm_bindgen(js_name = minifyBatch)]
+pub fn minify_batch(
+  queries: Vec<String>,
+  options: Option<MinifyOptions>,
+) -> Result<Vec<String>, GraphQLMinifyError> {
+  let minify = minifier(options)?;
+
+  queries.iter().map(|query| minify(query)).collect()
+}
+
+type Minifier = fn(&str) -> Result<String, GraphQLMinifyError>;
+
+fn minifier(options: Option<MinifyOptions>) -> Result<Minifier, GraphQLMinifyError> {
+  match options.and_then(|options| options.mode()).as_deref() {
+    None | Some("minify") => Ok(|query| {
+      graphql_minify::minify(query).map_err(|e| GraphQLMinifyError::lexing(e, Some(query)))
+    }),
+    Some("strict") => Ok(|query| {
+      graphql_minify::minify_strict(query).map_err(|e| GraphQLMinifyError::lexing(e, Some(query)))
+    }),
+    Some("normalize") => Ok(|query| {
+      graphql_minify::normalize(query).map_err(|e| GraphQLMinifyError::lexing(e, Some(query)))
+    }),
+    Some(mode) => Err(GraphQLMinifyError {
+      kind: GraphQLMinifyErrorKind::InvalidOptions,
+      message: format!("unknown minify mode \"{mode}\""),
+      offset: 0,
+      line: None,
+      column: None,
+    }),
+  }
+}
+
+/// Pretty-prints a document with two spaces of indentation, keeping its comments.
+#[wasm_bindgen]
+pub fn format(query: &str) -> Result<String, GraphQLMinifyError> {
+  graphql_minify::format(query).map_err(|e| GraphQLMinifyError::lexing(e, Some(query)))
+}
+
+/// Minifies a UTF-8 encoded document without going through a JS string.
+#[wasm_bindgen(js_name = minifyBytes)]
+pub fn minify_bytes(query: &[u8]) -> Result<Vec<u8>, GraphQLMinifyError> {
+  let query = std::str::from_utf8(query).map_err(|e| GraphQLMinifyError::utf8(e, 0))?;
+  let minified =
+    graphql_minify::minify(query).map_err(|e| GraphQLMinifyError::lexing(e, Some(query)))?;
+
+  Ok(minified.into_bytes())
+}
+
+/// Minifies a UTF-8 encoded document chunk by chunk, e.g. from a Node stream.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct MinifyStream {
+  inner: graphql_minify::MinifyStream,
+  pending: Vec<u8>,
+  offset: usize,
+}
+
+#[wasm_bindgen]
+impl MinifyStream {
+  #[wasm_bindgen(constructor)]
+  pub fn new() -> MinifyStream {
+    MinifyStream::default()
+  }
+
+  /// Returns the minified bytes that became available with this chunk, which may be empty.
+  pub fn write(&mut self, chunk: &[u8]) -> Result<Vec<u8>, GraphQLMinifyError> {
+    self.pending.extend_from_slice(chunk);
+
+    // A chunk may end in the middle of a multi-byte character, keep those bytes for the next one.
+    let valid = match std::str::from_utf8(&self.pending) {
+      Ok(text) => text.len(),
+      Err(e) if e.error_len().is_none() => e.valid_up_to(),
+      Err(e) => return Err(GraphQLMinifyError::utf8(e, self.offset)),
+    };
+    let text = std::str::from_utf8(&self.pending[..valid])
+      .map_err(|e| GraphQLMinifyError::utf8(e, self.offset))?;
+    let minified = self
+      .inner
+      .write(text)
+      .map_err(|e| GraphQLMinifyError::lexing(e, None))?;
+    self.pending.drain(..valid);
+    self.offset += valid;
+
+    Ok(minified.into_bytes())
+  }
+
+  /// Returns the rest of the minified document. The stream can not be used afterwards.
+  pub fn finish(self) -> Result<Vec<u8>, GraphQLMinifyError> {
+    if !self.pending.is_empty() {
+      return Err(GraphQLMinifyError {
+        kind: GraphQLMinifyErrorKind::InvalidUtf8,
+        message: "stream ended with an incomplete UTF-8 sequence".to_string(),
+        offset: self.offset,
+        line: None,
+        column: None,
+      });
+    }
+    let minified = self
+      .inner
+      .finish()
+      .map_err(|e| GraphQLMinifyError::lexing(e, None))?;
+
+    Ok(minified.into_bytes())
+  }
+}
+
 #[wasm_bindgen]
-pub fn minify(query: &str) -> String {
-  graphql_minify::minify(query).unwrap()
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GraphQLMinifyErrorKind {
+  UnknownToken,
+  UnterminatedString,
+  InvalidUtf8,
+  InvalidEscapeSequence,
+  InvalidNumber,
+  InvalidOptions,
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const GRAPHQL_MINIFY_ERROR: &'static str = r#"
+/**
+ * Thrown by every function of this package when a document can not be minified or the options are
+ * invalid.
+ *
+ * `offset` is a byte offset into the UTF-8 encoded document, it is `0` for `InvalidOptions`. `line`
+ * and `column` are one-based and only known when the whole document was passed at once, i.e. not
+ * for `MinifyStream`.
+ */
+export interface GraphQLMinifyError extends Error {
+  name: "GraphQLMinifyError";
+  kind: GraphQLMinifyErrorKind;
+  offset: number;
+  line: number | undefined;
+  column: number | undefined;
+}
+"#;
+
+#[wasm_bindgen(inline_js = r#"
+export class GraphQLMinifyError extends Error {
+  constructor(message, kind, offset, line, column) {
+    super(message);
+    this.name = "GraphQLMinifyError";
+    this.kind = kind;
+    this.offset = offset;
+    this.line = line;
+    this.column = column;
+  }
+}
+"#)]
+extern "C" {
+  #[wasm_bindgen(js_name = GraphQLMinifyError)]
+  type JsGraphQLMinifyError;
+
+  #[wasm_bindgen(constructor, js_class = GraphQLMinifyError)]
+  fn new(
+    message: &str,
+    kind: GraphQLMinifyErrorKind,
+    offset: usize,
+    line: Option<usize>,
+    column: Option<usize>,
+  ) -> JsGraphQLMinifyError;
+}
+
+/// Converted into a JS `GraphQLMinifyError`, a subclass of `Error`, when it is thrown.
+#[derive(Clone, Debug)]
+pub struct GraphQLMinifyError {
+  kind: GraphQLMinifyErrorKind,
+  message: String,
+  offset: usize,
+  line: Option<usize>,
+  column: Option<usize>,
+}
+
+impl From<GraphQLMinifyError> for JsValue {
+  fn from(error: GraphQLMinifyError) -> Self {
+    JsGraphQLMinifyError::new(
+      &error.message,
+      error.kind,
+      error.offset,
+      error.line,
+      error.column,
+    )
+    .into()
+  }
+}
+
+/// Returns whether `value` was thrown by this package.
+#[wasm_bindgen(js_name = isGraphQLMinifyError, skip_typescript)]
+pub fn is_graphql_minify_error(value: JsValue) -> bool {
+  value.is_instance_of::<JsGraphQLMinifyError>()
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const IS_GRAPHQL_MINIFY_ERROR: &'static str = r#"
+/**
+ * Returns whether `value` was thrown by this package.
+ */
+export function isGraphQLMinifyError(value: unknown): value is GraphQLMinifyError;
+"#;
+
+impl GraphQLMinifyError {
+  fn lexing(error: LexingError, source: Option<&str>) -> Self {
+    let kind = match error {
+      LexingError::UnknownToken(_) => GraphQLMinifyErrorKind::UnknownToken,
+      LexingError::UnterminatedString(_) => GraphQLMinifyErrorKind::UnterminatedString,
+      LexingError::InvalidEscapeSequence(_) => GraphQLMinifyErrorKind::InvalidEscapeSequence,
+      LexingError::InvalidNumber(_) => GraphQLMinifyErrorKind::InvalidNumber,
+      _ => GraphQLMinifyErrorKind::UnknownToken,
+    };
+    let location = source.map(|source| error.location(source));
+
+    GraphQLMinifyError {
+      kind,
+      message: error.to_string(),
+      offset: error.offset(),
+      line: location.map(|(line, _)| line),
+      column: location.map(|(_, column)| column),
+    }
+  }
+
+  fn utf8(error: Utf8Error, offset: usize) -> Self {
+    GraphQLMinifyError {
+      kind: GraphQLMinifyErrorKind::InvalidUtf8,
+      message: error.to_string(),
+      offset: offset + error.valid_up_to(),
+      line: None,
+      column: None,
+    }
+  }
 }
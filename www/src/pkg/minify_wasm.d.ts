@@ -1,39 +1,127 @@
 /* tslint:disable */
 /* eslint-disable */
+
 /**
-* @param {string} query
-* @returns {string}
-*/
-export function minify(query: string): string;
+ * Returns whether `value` was thrown by this package.
+ */
+export function isGraphQLMinifyError(value: unknown): value is GraphQLMinifyError;
+
+
+
+/**
+ * Thrown by every function of this package when a document can not be minified or the options are
+ * invalid.
+ *
+ * `offset` is a byte offset into the UTF-8 encoded document, it is `0` for `InvalidOptions`. `line`
+ * and `column` are one-based and only known when the whole document was passed at once, i.e. not
+ * for `MinifyStream`.
+ */
+export interface GraphQLMinifyError extends Error {
+    name: "GraphQLMinifyError";
+    kind: GraphQLMinifyErrorKind;
+    offset: number;
+    line: number | undefined;
+    column: number | undefined;
+}
+
+
+
+export interface MinifyOptions {
+    /**
+     * `"minify"` (the default) strips ignored characters, `"strict"` also rejects numbers that are
+     * directly followed by a name or a dot and punctuators outside of the spec such as `?`, and
+     * `"normalize"` returns the exact output of GraphQL.js'
+     * `stripIgnoredCharacters(print(parse(query)))`.
+     */
+    mode?: "minify" | "strict" | "normalize";
+}
+
+
+
+export enum GraphQLMinifyErrorKind {
+    UnknownToken = 0,
+    UnterminatedString = 1,
+    InvalidUtf8 = 2,
+    InvalidEscapeSequence = 3,
+    InvalidNumber = 4,
+    InvalidOptions = 5,
+}
+
+/**
+ * Minifies a UTF-8 encoded document chunk by chunk, e.g. from a Node stream.
+ */
+export class MinifyStream {
+    free(): void;
+    [Symbol.dispose](): void;
+    /**
+     * Returns the rest of the minified document. The stream can not be used afterwards.
+     */
+    finish(): Uint8Array;
+    constructor();
+    /**
+     * Returns the minified bytes that became available with this chunk, which may be empty.
+     */
+    write(chunk: Uint8Array): Uint8Array;
+}
+
+/**
+ * Pretty-prints a document with two spaces of indentation, keeping its comments.
+ */
+export function format(query: string): string;
+
+export function minify(query: string, options?: MinifyOptions | null): string;
+
+/**
+ * Minifies every document in `queries`, failing on the first one that can not be minified.
+ */
+export function minifyBatch(queries: string[], options?: MinifyOptions | null): string[];
+
+/**
+ * Minifies a UTF-8 encoded document without going through a JS string.
+ */
+export function minifyBytes(query: Uint8Array): Uint8Array;
 
 export type InitInput = RequestInfo | URL | Response | BufferSource | WebAssembly.Module;
 
 export interface InitOutput {
-  readonly memory: WebAssembly.Memory;
-  readonly minify: (a: number, b: number, c: number) => void;
-  readonly __wbindgen_add_to_stack_pointer: (a: number) => number;
-  readonly __wbindgen_malloc: (a: number, b: number) => number;
-  readonly __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
-  readonly __wbindgen_free: (a: number, b: number, c: number) => void;
+    readonly memory: WebAssembly.Memory;
+    readonly __wbg_minifystream_free: (a: number, b: number) => void;
+    readonly format: (a: number, b: number) => [number, number, number, number];
+    readonly isGraphQLMinifyError: (a: any) => number;
+    readonly minify: (a: number, b: number, c: number) => [number, number, number, number];
+    readonly minifyBatch: (a: number, b: number, c: number) => [number, number, number, number];
+    readonly minifyBytes: (a: number, b: number) => [number, number, number, number];
+    readonly minifystream_finish: (a: number) => [number, number, number, number];
+    readonly minifystream_new: () => number;
+    readonly minifystream_write: (a: number, b: number, c: number) => [number, number, number, number];
+    readonly __wbindgen_malloc: (a: number, b: number) => number;
+    readonly __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
+    readonly __wbindgen_externrefs: WebAssembly.Table;
+    readonly __externref_table_dealloc: (a: number) => void;
+    readonly __wbindgen_free: (a: number, b: number, c: number) => void;
+    readonly __externref_table_alloc: () => number;
+    readonly __externref_drop_slice: (a: number, b: number) => void;
+    readonly __wbindgen_start: () => void;
 }
 
 export type SyncInitInput = BufferSource | WebAssembly.Module;
+
+/**
+ * Instantiates the given `module`, which can either be bytes or
+ * a precompiled `WebAssembly.Module`.
+ *
+ * @param {{ module: SyncInitInput }} module - Passing `SyncInitInput` directly is deprecated.
+ *
+ * @returns {InitOutput}
+ */
+export function initSync(module: { module: SyncInitInput } | SyncInitInput): InitOutput;
+
 /**
-* Instantiates the given `module`, which can either be bytes or
-* a precompiled `WebAssembly.Module`.
-*
-* @param {SyncInitInput} module
-*
-* @returns {InitOutput}
-*/
-export function initSync(module: SyncInitInput): InitOutput;
-
-/**
-* If `module_or_path` is {RequestInfo} or {URL}, makes a request and
-* for everything else, calls `WebAssembly.instantiate` directly.
-*
-* @param {InitInput | Promise<InitInput>} module_or_path
-*
-* @returns {Promise<InitOutput>}
-*/
-export default function __wbg_init (module_or_path?: InitInput | Promise<InitInput>): Promise<InitOutput>;
+ * If `module_or_path` is {RequestInfo} or {URL}, makes a request and
+ * for everything else, calls `WebAssembly.instantiate` directly.
+ *
+ * @param {{ module_or_path: InitInput | Promise<InitInput> }} module_or_path - Passing `InitInput` directly is deprecated.
+ *
+ * @returns {Promise<InitOutput>}
+ */
+export default function __wbg_init (module_or_path?: { module_or_path: InitInput | Promise<InitInput> } | InitInput | Promise<InitInput>): Promise<InitOutput>;
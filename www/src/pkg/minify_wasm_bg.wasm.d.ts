@@ -1,8 +1,20 @@
 /* tslint:disable */
 /* eslint-disable */
 export const memory: WebAssembly.Memory;
-export function minify(a: number, b: number, c: number): void;
-export function __wbindgen_add_to_stack_pointer(a: number): number;
-export function __wbindgen_malloc(a: number, b: number): number;
-export function __wbindgen_realloc(a: number, b: number, c: number, d: number): number;
-export function __wbindgen_free(a: number, b: number, c: number): void;
+export const __wbg_minifystream_free: (a: number, b: number) => void;
+export const format: (a: number, b: number) => [number, number, number, number];
+export const isGraphQLMinifyError: (a: any) => number;
+export const minify: (a: number, b: number, c: number) => [number, number, number, number];
+export const minifyBatch: (a: number, b: number, c: number) => [number, number, number, number];
+export const minifyBytes: (a: number, b: number) => [number, number, number, number];
+export const minifystream_finish: (a: number) => [number, number, number, number];
+export const minifystream_new: () => number;
+export const minifystream_write: (a: number, b: number, c: number) => [number, number, number, number];
+export const __wbindgen_malloc: (a: number, b: number) => number;
+export const __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
+export const __wbindgen_externrefs: WebAssembly.Table;
+export const __externref_table_dealloc: (a: number) => void;
+export const __wbindgen_free: (a: number, b: number, c: number) => void;
+export const __externref_table_alloc: () => number;
+export const __externref_drop_slice: (a: number, b: number) => void;
+export const __wbindgen_start: () => void;
@@ -0,0 +1,11 @@
+
+export class GraphQLMinifyError extends Error {
+  constructor(message, kind, offset, line, column) {
+    super(message);
+    this.name = "GraphQLMinifyError";
+    this.kind = kind;
+    this.offset = offset;
+    this.line = line;
+    this.column = column;
+  }
+}